@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    arc_inner,
+    is_optional_source,
+    source_field,
+};
+
+
+/// Adds `from` and `with` options to the `#[error_from]` or `#[error_transparent]`
+/// attribute to wrap source error into `Arc` in the `From` implementation
+fn wrap_from_attr(attr: &mut syn::Attribute, inner: &syn::Type) -> syn::Result<()> {
+    if let Ok(syn::Meta::List(v)) = attr.parse_meta() {
+        let has_with = v.nested.iter().any(|v| matches!(v,
+            syn::NestedMeta::Meta(syn::Meta::NameValue(v))
+                if v.path.is_ident("with") || v.path.is_ident("from")));
+        if has_with {
+            let message = "error_clone could not be used with with and from options";
+            return Err(syn::Error::new_spanned(attr, message))
+        }
+    }
+
+    let from = quote! { #inner }.to_string();
+    let options = quote! {
+        from = #from, with = "::std::sync::Arc::new"
+    };
+    attr.tokens = match syn::parse2::<proc_macro2::Group>(attr.tokens.clone()) {
+        Ok(v) => {
+            let nested = v.stream();
+            quote! { ( #options, #nested ) }
+        }
+        Err(_) => quote! { ( #options ) },
+    };
+
+    Ok(())
+}
+
+
+/// Wraps type of the `#[error_source(optional)]` field from `Option<T>` into `Option<Arc<T>>`
+fn wrap_optional(field: &mut syn::Field) {
+    if let syn::Type::Path(v) = &mut field.ty {
+        let segment = v.path.segments.last_mut().unwrap();
+        if let syn::PathArguments::AngleBracketed(v) = &mut segment.arguments {
+            if let Some(syn::GenericArgument::Type(ty)) = v.args.first_mut() {
+                let inner = arc_inner(ty).unwrap_or(ty).clone();
+                *ty = syn::parse_quote! { ::std::sync::Arc<#inner> };
+            }
+        }
+    }
+}
+
+
+/// Wraps source of the variant or struct into `Arc`
+fn wrap_fields(attrs: &mut [syn::Attribute], fields: &mut syn::Fields) -> syn::Result<()> {
+    for field in fields.iter_mut().filter(|v| is_optional_source(v)) {
+        wrap_optional(field);
+    }
+
+    let attr = attrs.iter_mut()
+        .find(|v| v.path.is_ident("error_from") || v.path.is_ident("error_transparent"));
+    let attr = match attr {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+
+    let idx = source_field(fields)?.0;
+    let field = fields.iter_mut().nth(idx).unwrap();
+    let inner = arc_inner(&field.ty).unwrap_or(&field.ty).clone();
+    field.ty = syn::parse_quote! { ::std::sync::Arc<#inner> };
+
+    wrap_from_attr(attr, &inner)
+}
+
+
+/// Wraps sources into `Arc` and derives `Clone`
+pub fn impl_error_clone(mut item: syn::DeriveInput) -> TokenStream {
+    let result = match &mut item.data {
+        syn::Data::Enum(v) => v.variants.iter_mut()
+            .try_for_each(|v| wrap_fields(&mut v.attrs, &mut v.fields)),
+        syn::Data::Struct(v) => wrap_fields(&mut item.attrs, &mut v.fields),
+        syn::Data::Union(v) => Err(syn::Error::new_spanned(v.union_token, "error_clone required enum or struct")),
+    };
+    let error = match result {
+        Ok(_) => TokenStream::new(),
+        Err(e) => e.to_compile_error(),
+    };
+
+    let has_clone = item.attrs.iter()
+        .filter(|v| v.path.is_ident("derive"))
+        .filter_map(|v| v.parse_meta().ok())
+        .any(|v| match v {
+            syn::Meta::List(v) => v.nested.iter().any(|v| matches!(v,
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("Clone"))),
+            _ => false,
+        });
+    if ! has_clone {
+        item.attrs.push(syn::parse_quote! { #[derive(Clone)] });
+    }
+
+    quote! {
+        #error
+        #item
+    }
+}
@@ -74,10 +74,28 @@
 //!
 //! `#[error_from]` and `#[error_kind]` contain list of attributes to display error.
 //! First attribute should be literal string. Other attributes is a number of the
-//! unnamed field in the tuple. Started from 0.
+//! unnamed field in the tuple. Started from 0. For variants with named fields
+//! attributes is a name of the field.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: not found: {} code:{}", path, code)]
+//!     NotFound { path: String, code: u32 },
+//! }
+//!
+//! let error = AppError::NotFound { path: "a.txt".to_owned(), code: 404 };
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: not found: a.txt code:404");
+//! ```
 //!
 //! `#[error_from]` could defined without attributes it's equal to `#[error_from("{}", 0)]`
 //!
+//! Format string of the `#[error_from]` could refer to the wrapped error with
+//! named argument `{source}`: `#[error_from("App IO: {source}")]`
+//!
 //! ## Error prefix
 //!
 //! `#[error_prefix]` attribute should be defined before enum declaration and
@@ -140,268 +158,7503 @@
 //! assert_eq!(error.to_string().as_str(),
 //!     "App: Mod: No such file or directory (os error 2)");
 //! ```
+//!
+//! Enum attribute `#[error_chain]` implements methods to walk through the sources.
+//! Method `iter_chain()` returns iterator over the error and its sources.
+//! Method `root_cause()` returns the innermost source or error itself.
+//! Method `display_chain()` returns wrapper to display error with
+//! its sources separated with `: `.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_chain]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from("read failed")]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.iter_chain().count(), 2);
+//! assert!(error.root_cause().downcast_ref::<std::io::Error>().is_some());
+//! assert_eq!(error.display_chain().to_string().as_str(),
+//!     "App: read failed: entity not found");
+//! ```
+//!
+//! ## Fingerprint
+//!
+//! Enum attribute `#[error_fingerprint]` implements `fingerprint()` method
+//! which returns stable hash of the error type, variant name and kind of the
+//! innermost `std::io::Error`. Payloads are not included, so it could be used
+//! to group recurring errors.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_fingerprint]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: not found: {}", 0)]
+//!     NotFound(String),
+//! }
+//!
+//! let e1 = AppError::NotFound("a.txt".to_owned());
+//! let e2 = AppError::NotFound("b.txt".to_owned());
+//! assert_eq!(e1.fingerprint(), e2.fingerprint());
+//!
+//! let e3: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_ne!(e1.fingerprint(), e3.fingerprint());
+//! ```
+//!
+//! ## Remote errors
+//!
+//! `#[error_remote]` attribute should be defined before enum declaration.
+//! It describes a local mirror of the error type defined in other crate.
+//! Mirror should contain same variants with same fields,
+//! macro implements conversion from the remote type into the mirror.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! mod other {
+//!     #[derive(Debug)]
+//!     pub enum Error {
+//!         Closed,
+//!         NotFound(String),
+//!     }
+//! }
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "Other"]
+//! #[error_remote(other::Error)]
+//! enum OtherError {
+//!     #[error_kind("connection closed")]
+//!     Closed,
+//!     #[error_kind("not found: {}", 0)]
+//!     NotFound(String),
+//! }
+//!
+//! fn example() -> Result<(), other::Error> {
+//!     Err(other::Error::NotFound("a.txt".to_owned()))
+//! }
+//!
+//! let error: OtherError = example().unwrap_err().into();
+//! assert_eq!(error.to_string().as_str(),
+//!     "Other: not found: a.txt");
+//! ```
+//!
+//! ## Crash report
+//!
+//! With the `crash-report` feature enum attribute `#[error_crash_report]` implements
+//! `write_crash_report()` and `crash_report()` methods. Report file contains package
+//! name and version, OS, chain of the error, attachments and backtrace.
+//! File saved into the temporary directory.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_crash_report]
+//! enum AppError {
+//!     #[error_from("App: {}", 0)]
+//!     Io(std::io::Error),
+//! }
+//!
+//! fn main() {
+//!     if let Err(e) = run() {
+//!         e.crash_report(&[("config", &config_text)]);
+//!         std::process::exit(1);
+//!     }
+//! }
+//! ```
+//!
+//! ## Default format
+//!
+//! `#[error_format]` attribute should be defined before enum declaration and
+//! sets display format for variants without `#[error_from]` or `#[error_kind]`.
+//! Format string could contain `{variant}` with the variant name,
+//! `{fields}` with comma separated values of the variant fields
+//! (`name: value` for named fields) and `{prefix}` with the `#[error_prefix]`
+//! value. With `{prefix}` the prefix is placed by the template instead of
+//! the beginning of the message. Other placeholders are not allowed.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! #[error_format("{variant} ({fields})")]
+//! enum AppError {
+//!     NotFound(String),
+//!     Timeout(u64, u64),
+//!     #[error_kind("custom message")]
+//!     Custom,
+//! }
+//!
+//! let error = AppError::Timeout(10, 20);
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: Timeout (10, 20)");
+//!
+//! let error = AppError::Custom;
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: custom message");
+//! ```
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_format("{name}: {fields}")]
+//! enum AppError {
+//!     NotFound(String),
+//! }
+//! ```
+//!
+//! ## Conversion audit
+//!
+//! `#[error_audit]` attribute should be defined before enum declaration.
+//! Macro implements hidden `ERROR_CONVERSIONS` constant with list of the
+//! generated `From` conversions: source type and variant name.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_audit]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_from]
+//!     Utf8(std::str::Utf8Error),
+//! }
+//!
+//! assert_eq!(AppError::ERROR_CONVERSIONS, &[
+//!     ("std::io::Error", "Io"),
+//!     ("std::str::Utf8Error", "Utf8"),
+//! ]);
+//! ```
+//!
+//! ## Error groups
+//!
+//! `#[error_group]` attribute groups variants under the label.
+//! Group name appends into error text after the prefix and
+//! returns by the `group()` method.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_group("storage")]
+//!     #[error_kind("disk full")]
+//!     DiskFull,
+//!     #[error_kind("unknown")]
+//!     Unknown,
+//! }
+//!
+//! let error = AppError::DiskFull;
+//! assert_eq!(error.group(), Some("storage"));
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: storage: disk full");
+//!
+//! let error = AppError::Unknown;
+//! assert_eq!(error.group(), None);
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: unknown");
+//! ```
+//!
+//! ## Truncation
+//!
+//! `#[error_truncate]` attribute limits length of the error text.
+//! Longer text is cut and ends with `...`.
+//! Attribute could be defined before enum declaration for all variants
+//! or on the variant.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! #[error_truncate(64)]
+//! enum AppError {
+//!     #[error_truncate(16)]
+//!     #[error_kind("bad input: {}", 0)]
+//!     BadInput(String),
+//! }
+//!
+//! let error = AppError::BadInput("0123456789".to_owned());
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: bad input: ...");
+//! ```
+//!
+//! On the field `#[error_truncate]` limits length of the field text in the message.
+//! Field is formatted with `Display`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_kind("bad input: {}", 0)]
+//!     BadInput(#[error_truncate(4)] String),
+//! }
+//!
+//! let error = AppError::BadInput("0123456789".to_owned());
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: bad input: 0123...");
+//! ```
+//!
+//! ## Kind equality
+//!
+//! `#[error_eq_kind]` attribute should be defined before enum declaration.
+//! Macro implements `eq_kind()` method to compare variants and payload fields.
+//! Errors wrapped with `#[error_from]` are not compared.
+//! Payload fields should implement `PartialEq`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_eq_kind]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: code:{}", 0)]
+//!     Code(usize),
+//! }
+//!
+//! let e1: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! let e2: AppError = std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
+//! assert!(e1.eq_kind(&e2));
+//!
+//! assert!(AppError::Code(404).eq_kind(&AppError::Code(404)));
+//! assert!(! AppError::Code(404).eq_kind(&AppError::Code(500)));
+//! assert!(! AppError::Code(404).eq_kind(&e1));
+//! ```
+//!
+//! ## Snapshot
+//!
+//! `#[error_snapshot]` attribute should be defined before enum declaration.
+//! Macro implements `snapshot()` method which returns multiline text with type,
+//! variant, message and chain of the error. Output does not depend on the `Debug`
+//! implementation of the payload, so it is suitable for snapshot tests.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! #[error_snapshot]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.snapshot().as_str(), "\
+//! type: AppError
+//! variant: Io
+//! message: App: entity not found
+//! caused by: entity not found
+//! ");
+//! ```
+//!
+//! `chain_diff(expected)` method compares messages of the error chain with
+//! the expected list level by level. It returns `None` if chains are equal,
+//! or report with mismatching, missing and extra levels for the test log.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! #[error_snapshot]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.chain_diff(&["App: entity not found", "entity not found"]), None);
+//! assert_eq!(error.chain_diff(&["App: timed out"]).unwrap().as_str(), "\
+//! level 0: expected \"App: timed out\", found \"App: entity not found\"
+//! level 1: extra \"entity not found\"
+//! ");
+//! ```
+//!
+//! ## Examples
+//!
+//! `#[error_examples]` attribute should be defined before enum declaration.
+//! Macro implements `examples()` method which returns unit variants and
+//! variants marked with `#[error_example]` with `Default::default()` values
+//! of the fields. It helps to check `Display` of the each variant in tests.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_examples]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_example]
+//!     #[error_kind("App: bad request: {}", 0)]
+//!     BadRequest(String),
+//!     #[error_kind("App: closed")]
+//!     Closed,
+//! }
+//!
+//! let list: Vec<String> = AppError::examples().iter().map(ToString::to_string).collect();
+//! assert_eq!(list, vec!["App: bad request: ", "App: closed"]);
+//! ```
+//!
+//! ## Timeout and cancellation
+//!
+//! `#[error_timeout]` and `#[error_cancelled]` attributes mark variants for
+//! timeout and cancellation errors, for example wrapped `tokio::time::error::Elapsed`.
+//! Macro implements `is_timeout()` and `is_cancelled()` methods.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_timeout]
+//!     #[error_kind("App: request timed out after {}s", 0)]
+//!     Timeout(u64),
+//!     #[error_cancelled]
+//!     #[error_kind("App: request cancelled")]
+//!     Cancelled,
+//! }
+//!
+//! assert!(AppError::Timeout(30).is_timeout());
+//! assert!(! AppError::Timeout(30).is_cancelled());
+//! assert!(AppError::Cancelled.is_cancelled());
+//! ```
+//!
+//! `#[error_timeout(deadline)]` requires named field `deadline` with type
+//! `core::time::Duration`. Macro implements `{Enum}DeadlineExt` trait for any
+//! `Result` with error converted into the enum. `deadline(timeout)` method
+//! converts error and stores `timeout` in the `deadline` field, so configured
+//! timeout could be used in the message.
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_timeout(deadline)]
+//!     #[error_from(default, "App: request timed out after {:?}", deadline)]
+//!     Timeout {
+//!         source: std::io::Error,
+//!         deadline: Duration,
+//!     },
+//! }
+//!
+//! fn wait() -> Result<(), std::io::Error> {
+//!     Err(std::io::ErrorKind::TimedOut.into())
+//! }
+//!
+//! let error = wait().deadline(Duration::from_secs(30)).unwrap_err();
+//! assert!(error.is_timeout());
+//! assert_eq!(error.to_string().as_str(), "App: request timed out after 30s");
+//! ```
+//!
+//! ## Map errors
+//!
+//! `#[map_errors]` attribute could be defined on the function or impl block.
+//! Each `?` in the function body converts error into the given type first,
+//! then into the return type of the function. It helps to implement traits with
+//! the fixed error type, for example `std::io::Read`. `?` on `Option` is not changed.
+//! Closures, async blocks and nested items are not changed.
+//!
+//! Variant attribute `#[error_into_io]` on the `std::io::Error` source returns
+//! original error on conversion back into `std::io::Error`, other variants
+//! wrapped into `std::io::Error` with the `Other` kind.
+//!
+//! ```rust
+//! use error_rules::*;
+//! use std::io::Read;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "Reader"]
+//! enum ReaderError {
+//!     #[error_from]
+//!     #[error_into_io]
+//!     Io(std::io::Error),
+//!     #[error_from]
+//!     Utf8(std::str::Utf8Error),
+//! }
+//!
+//! struct Reader {
+//!     data: Vec<u8>,
+//! }
+//!
+//! #[map_errors(ReaderError)]
+//! impl Read for Reader {
+//!     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+//!         let text = std::str::from_utf8(&self.data)?;
+//!         buf[.. text.len()].copy_from_slice(text.as_bytes());
+//!         Ok(text.len())
+//!     }
+//! }
+//!
+//! let mut reader = Reader { data: vec![0xFF] };
+//! let error = reader.read(&mut [0; 1]).unwrap_err();
+//! assert_eq!(error.to_string().as_str(),
+//!     "Reader: invalid utf-8 sequence of 1 bytes from index 0");
+//! ```
+//!
+//! ## Lazy display
+//!
+//! Field with type `Box<dyn Fn(&mut std::fmt::Formatter) -> std::fmt::Result + Send + Sync>`
+//! could be used in the display attributes. Function is called only when
+//! error is displayed. Such fields do not implement `Debug`, so it should be
+//! implemented manually.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Error)]
+//! enum AppError {
+//!     #[error_kind("App: invalid config: {}", 0)]
+//!     Config(Box<dyn Fn(&mut std::fmt::Formatter) -> std::fmt::Result + Send + Sync>),
+//! }
+//!
+//! impl std::fmt::Debug for AppError {
+//!     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+//!         f.write_str("AppError::Config")
+//!     }
+//! }
+//!
+//! let config = vec![("port", 0)];
+//! let error = AppError::Config(Box::new(move |f| {
+//!     for (key, value) in &config {
+//!         write!(f, "{} = {}", key, value)?;
+//!     }
+//!     Ok(())
+//! }));
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: invalid config: port = 0");
+//! ```
+//!
+//! ## Merge errors
+//!
+//! `merge_errors!` macro declares enum with one `#[error_transparent]` variant for
+//! each error type. Variant named by the type name, for `module::Error` by the
+//! module name: `std::io::Error` is `Io`. Other name could be defined as
+//! `Name: Type`. Attributes defined before the name are passed to the enum.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "Db"]
+//! enum DbError {
+//!     #[error_kind("connection lost")]
+//!     ConnectionLost,
+//! }
+//!
+//! merge_errors!(pub AppError = DbError + std::io::Error + Utf8: std::str::Utf8Error);
+//!
+//! let error: AppError = DbError::ConnectionLost.into();
+//! assert_eq!(error.to_string().as_str(),
+//!     "Db: connection lost");
+//!
+//! let error = AppError::Io(std::io::Error::from(std::io::ErrorKind::NotFound));
+//! assert_eq!(error.to_string().as_str(),
+//!     "entity not found");
+//! ```
+//!
+//! Variants with same name are compile errors.
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! // error: variant Fmt already defined, use `Name: Type` to define variant name
+//! merge_errors!(AppError = std::fmt::Error + Fmt: std::str::Utf8Error);
+//! ```
+//!
+//! ## Bytes display
+//!
+//! Fields with bytes, like `Vec<u8>` or `&[u8]`, could be displayed with adapters:
+//!
+//! - `hex(N)` - hex dump of the field `N`, limited to 32 bytes by default.
+//!   Limit could be defined with second argument: `hex(N, 8)`
+//! - `utf8(N)` - text of the field `N`, invalid UTF-8 sequences replaced with `U+FFFD`
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: bad frame: {}", hex(0, 4))]
+//!     BadFrame(Vec<u8>),
+//!     #[error_kind("App: bad line: {}", utf8(0))]
+//!     BadLine(Vec<u8>),
+//! }
+//!
+//! let error = AppError::BadFrame(vec![0x47, 0x00, 0x11, 0x10, 0xFF, 0xFF]);
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: bad frame: 47001110... (6 bytes)");
+//!
+//! let error = AppError::BadLine(b"GET /\xFF".to_vec());
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: bad line: GET /\u{FFFD}");
+//! ```
+//!
+//! ## IO error class
+//!
+//! `#[error_io_class]` attribute should be defined before enum declaration.
+//! Macro declares `{Name}IoClass` enum and implements `io_class()` method which
+//! returns class of the first `std::io::Error` in the error chain.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_io_class]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: custom")]
+//!     Custom,
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::TimedOut).into();
+//! assert_eq!(error.io_class(), Some(AppErrorIoClass::Timeout));
+//! assert_eq!(AppError::Custom.io_class(), None);
+//! ```
+//!
+//! ## Generics
+//!
+//! Enums with type parameters, bounds and where clauses are supported.
+//! Generated implementations keep the same parameters.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "Store"]
+//! enum StoreError<B: std::error::Error + 'static> {
+//!     #[error_from]
+//!     Backend(B),
+//! }
+//!
+//! let error: StoreError<std::io::Error> =
+//!     std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.to_string().as_str(),
+//!     "Store: entity not found");
+//! ```
+//!
+//! ## Source field
+//!
+//! `#[error_from]` variant could have several fields. Then field with error
+//! source should be marked with `#[error_source]` attribute or named `source`.
+//! `From` implementation is not generated for variant with other fields.
+//! Option `default` generates `From` which fills other fields with `Default::default()`:
+//! `#[error_from(default, "Open {}: {}", 0, 1)]`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from("Open {}: {}", 0, 1)]
+//!     Open(String, #[error_source] std::io::Error),
+//! }
+//!
+//! let error = AppError::Open("/tmp/app.conf".to_owned(),
+//!     std::io::Error::from(std::io::ErrorKind::NotFound));
+//! assert_eq!(error.to_string().as_str(),
+//!     "Open /tmp/app.conf: entity not found");
+//! ```
+//!
+//! Variant without source field is compile error.
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     // error: variant with multiple fields should mark source with #[error_source]
+//!     #[error_from("Open {}: {}", 0, 1)]
+//!     Open(String, std::io::Error),
+//! }
+//! ```
+//!
+//! ## Conversion function
+//!
+//! Types without `std::error::Error` implementation could be converted with
+//! function. Option `with` defines function and option `from` defines its
+//! argument type. Result of the function stored in the variant.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! pub struct Errno(i32);
+//!
+//! fn errno_to_io(e: Errno) -> std::io::Error {
+//!     std::io::Error::from_raw_os_error(e.0)
+//! }
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from("System: {}", 0, with = "errno_to_io", from = "Errno")]
+//!     System(std::io::Error),
+//! }
+//!
+//! let error: AppError = Errno(2).into();
+//! assert!(error.to_string().starts_with("System: "));
+//! ```
+//!
+//! ## Boxed source
+//!
+//! `#[error_from]` accepts boxed errors like `Box<dyn Error + Send + Sync>`.
+//! Method `source()` returns the boxed error itself.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from]
+//!     Other(Box<dyn std::error::Error + Send + Sync>),
+//! }
+//!
+//! let error: AppError = Box::<dyn std::error::Error + Send + Sync>::from("failed").into();
+//! assert_eq!(error.to_string().as_str(), "App: failed");
+//! ```
+//!
+//! ## Structs
+//!
+//! Derive could be used with structs. Attributes `#[error_from]` and
+//! `#[error_kind]` defined on the struct itself.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_from("Config {}: {source}", path)]
+//! struct ConfigError {
+//!     path: String,
+//!     #[error_source]
+//!     error: std::io::Error,
+//! }
+//!
+//! let error = ConfigError {
+//!     path: "/etc/app.conf".to_owned(),
+//!     error: std::io::Error::from(std::io::ErrorKind::NotFound),
+//! };
+//! assert_eq!(error.to_string().as_str(),
+//!     "Config /etc/app.conf: entity not found");
+//! ```
+//!
+//! ## Map fields
+//!
+//! Enum attribute `#[error_map]` implements `map_{variant}()` method for each variant
+//! with fields. Method calls function with mutable references to the variant fields.
+//! Source error and other variants are kept as is.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_map]
+//! enum AppError {
+//!     #[error_from("Open {}: {}", 0, 1)]
+//!     Open(String, #[error_source] std::io::Error),
+//! }
+//!
+//! let error = AppError::Open("/home/user/secret".to_owned(),
+//!     std::io::Error::from(std::io::ErrorKind::NotFound));
+//! let error = error.map_open(|path| *path = "<redacted>".to_owned());
+//! assert_eq!(error.to_string().as_str(),
+//!     "Open <redacted>: entity not found");
+//! ```
+//!
+//! ## Transparent
+//!
+//! Variant with `#[error_transparent]` attribute forwards `Display` and `source()`
+//! to the inner error. Error prefix is not used for this variant.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "Inner"]
+//! enum InnerError {
+//!     #[error_kind("failed")]
+//!     Failed,
+//! }
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_transparent]
+//!     Inner(InnerError),
+//! }
+//!
+//! let error: AppError = InnerError::Failed.into();
+//! assert_eq!(error.to_string().as_str(), "Inner: failed");
+//! ```
+//!
+//! ## Without From
+//!
+//! Option `no_from` skips `From` implementation for the variant.
+//! Display and `source()` work as usual. Useful when several variants
+//! wraps the same error type.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from("Read: {}", 0)]
+//!     Read(std::io::Error),
+//!     #[error_from("Write: {}", 0, no_from)]
+//!     Write(std::io::Error),
+//! }
+//!
+//! let error = AppError::Write(std::io::Error::from(std::io::ErrorKind::NotFound));
+//! assert_eq!(error.to_string().as_str(), "Write: entity not found");
+//! ```
+//!
+//! ## Builder
+//!
+//! Variant with named fields and `#[error_builder]` attribute gets builder.
+//! Method with variant name in snake_case returns `{Name}{Variant}Builder`.
+//! Each field have setter with same name. Method `build()` returns `Ok` with error
+//! or `Err` with `{Name}MissingField` if required field is not defined.
+//! Fields with `Option` type are not required.
+//! For structs method named `builder()` and returns `{Name}Builder`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_builder]
+//!     #[error_kind("Query {} failed", sql)]
+//!     QueryFailed {
+//!         sql: String,
+//!         rows: Option<usize>,
+//!     },
+//! }
+//!
+//! let error = AppError::query_failed()
+//!     .sql("SELECT 1".to_owned())
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(error.to_string().as_str(), "Query SELECT 1 failed");
+//!
+//! let missing = AppError::query_failed().build().unwrap_err();
+//! assert_eq!(missing.to_string().as_str(),
+//!     "AppErrorQueryFailedBuilder: field sql required");
+//! ```
+//!
+//! ## Primary variant
+//!
+//! If several variants wraps the same error type, option `primary` selects variant
+//! for the `From` implementation. Other variants with same type constructed explicitly.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from("Read: {}", 0, primary)]
+//!     Read(std::io::Error),
+//!     #[error_from("Write: {}", 0)]
+//!     Write(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.to_string().as_str(), "Read: entity not found");
+//! ```
+//!
+//! ## Inline arguments
+//!
+//! Format string could refer to the named fields with `{name}`
+//! and to the unnamed fields with `{0}` if arguments are not defined.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: missing key {key} in section {section}")]
+//!     MissingKey { key: String, section: String },
+//!     #[error_kind("App: bad value {1:?} at line {0}")]
+//!     BadValue(usize, String),
+//! }
+//!
+//! let error = AppError::MissingKey {
+//!     key: "port".to_owned(),
+//!     section: "server".to_owned(),
+//! };
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: missing key port in section server");
+//!
+//! let error = AppError::BadValue(12, "x".to_owned());
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: bad value \"x\" at line 12");
+//! ```
+//!
+//! ## Delegate
+//!
+//! Enum attribute `#[error_delegate]` defines public error over the inner error.
+//! Enum should have single variant with inner error. `Display` and `source()`
+//! forwarded to the inner error, `#[error_prefix]` of the public error is
+//! added before the inner message. Inner type is not exposed: accessors
+//! generated for the inner error are forwarded only when listed in the
+//! attribute, e.g. `#[error_delegate(code, variant_name)]`. Allowed accessors:
+//! `code`, `status`, `variant_name`, `group`, `help`, `url`, `is_timeout`,
+//! `is_cancelled`, `is_transient`, `fingerprint`, `exit_code`, `severity`,
+//! `kind` and `category`. Last three return types generated for the inner
+//! error, e.g. `internal::ErrorSeverity`, so the inner error should be
+//! declared with a type path without generics.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! mod internal {
+//!     use error_rules::*;
+//!
+//!     #[derive(Debug, Error)]
+//!     pub enum Error {
+//!         #[error_kind("Internal: closed")]
+//!         #[error_code = 10]
+//!         Closed,
+//!     }
+//! }
+//!
+//! #[derive(Debug, Error)]
+//! #[error_delegate(code)]
+//! pub enum PublicError {
+//!     Inner(internal::Error),
+//! }
+//!
+//! let error: PublicError = internal::Error::Closed.into();
+//! assert_eq!(error.to_string().as_str(), "Internal: closed");
+//! assert_eq!(error.code(), 10);
+//! ```
+//!
+//! ## Expressions
+//!
+//! Display arguments could be expressions with field as receiver:
+//! `0.kind()` for unnamed fields or `path.display()` for named fields.
+//! Error itself available as `self`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from("App IO {:?}: {}", 0.kind(), 0)]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: open {} failed", path.display())]
+//!     Open { path: std::path::PathBuf },
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.to_string().as_str(),
+//!     "App IO NotFound: entity not found");
+//!
+//! let error = AppError::Open { path: "/tmp/app.conf".into() };
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: open /tmp/app.conf failed");
+//! ```
+//!
+//! ## Format specs
+//!
+//! Format string passed to the `write!` macro as is, so format specs
+//! like `{:?}`, `{:x}`, `{:>8}` could be used. Width and precision could
+//! refer to the fields: `{:>1$}` or `{:>width$}`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: code {:#06x} data {:?}", 0, 1)]
+//!     Code(u16, Vec<u8>),
+//!     #[error_kind("App: [{name:>width$}]")]
+//!     Name { name: String, width: usize },
+//! }
+//!
+//! let error = AppError::Code(0x1f, vec![1, 2]);
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: code 0x001f data [1, 2]");
+//!
+//! let error = AppError::Name { name: "a".to_owned(), width: 3 };
+//! assert_eq!(error.to_string().as_str(), "App: [  a]");
+//! ```
+//!
+//! ## Validation
+//!
+//! Macro checks placeholders of the format string, field indexes and field names.
+//! Precision `{:.*}` takes two arguments: precision and value.
+//! Errors reported on the attribute.
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     // error: format string requires 2 arguments but 1 defined
+//!     #[error_kind("App: code:{} msg:{}", 0)]
+//!     Code(u8, String),
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     // error: field name not found
+//!     #[error_kind("App: open {}", name)]
+//!     Open { path: String },
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     // error: format string requires 3 arguments but 2 defined
+//!     #[error_kind("App: value {:.*} of {}", 0, 1)]
+//!     Value(usize, f64),
+//! }
+//! ```
+//!//!
+//! Attributes with unexpected format, or used without required crate feature,
+//! reported on the attribute as well.
+//!
+//! ```compile_fail
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     // error: meta format mismatch
+//!     #[error_kind("App: closed")]
+//!     #[error_code("10")]
+//!     Closed,
+//! }
+//! ```
+//!
+//! ## Doc comments
+//!
+//! Variant attribute `#[error_doc]` uses doc comment as format string.
+//! Lines of the comment joined with space. Fields could be used as `{name}` or `{0}`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     /// missing key {key}
+//!     /// in section {section}
+//!     #[error_doc]
+//!     MissingKey { key: String, section: String },
+//! }
+//!
+//! let error = AppError::MissingKey {
+//!     key: "port".to_owned(),
+//!     section: "server".to_owned(),
+//! };
+//! assert_eq!(error.to_string().as_str(),
+//!     "App: missing key port in section server");
+//! ```
+//!
+//! ## Alternate format
+//!
+//! Alternate format `{:#}` prints error with chain of the sources.
+//! Each source on the new line.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from("failed to read config")]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(format!("{:#}", error).as_str(),
+//!     "App: failed to read config\n    Caused by: entity not found");
+//! ```
+//!
+//! ## Automatic prefix
+//!
+//! `#[error_prefix(auto)]` makes prefix from the enum name without `Error` suffix.
+//! If enum named `Error` prefix is a name of the module.
+//! `#[error_prefix(module)]` always uses name of the module.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix(auto)]
+//! enum ParserError {
+//!     #[error_kind("bad token")]
+//!     BadToken,
+//! }
+//!
+//! assert_eq!(ParserError::BadToken.to_string().as_str(),
+//!     "Parser: bad token");
+//! ```
+//!
+//! ## Path fields
+//!
+//! Fields with `std::path::Path` or `std::path::PathBuf` type displayed with
+//! `display()` method. Other types named `Path` should implement `Display`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: cannot open {path}")]
+//!     Open { path: std::path::PathBuf },
+//! }
+//!
+//! let error = AppError::Open { path: "/tmp/app.conf".into() };
+//! assert_eq!(error.to_string().as_str(), "App: cannot open /tmp/app.conf");
+//! ```
+//!
+//! ## Constructors
+//!
+//! Enum attribute `#[error_constructors]` implements constructor for each variant.
+//! Constructor name is a variant name in snake_case, `HTTPError` into `http_error`.
+//! Keywords used as raw identifiers, for example `r#type()`.
+//! Arguments are fields of the variant.
+//! For structs constructor named `new()`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_constructors]
+//! enum AppError {
+//!     #[error_kind("App: http {}: {}", 0, 1)]
+//!     HttpStatus(u16, String),
+//! }
+//!
+//! let error = AppError::http_status(404, "not found".to_owned());
+//! assert_eq!(error.to_string().as_str(), "App: http 404: not found");
+//! ```
+//!
+//! ## Accessors
+//!
+//! Enum attribute `#[error_accessors]` implements methods for each variant:
+//! `is_{variant}()` checks variant, `as_{variant}()` and `as_{variant}_mut()`
+//! returns reference to the field or tuple with references to the fields.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_accessors]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: closed")]
+//!     Closed,
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert!(error.is_io());
+//! assert!(! error.is_closed());
+//! assert_eq!(error.as_io().map(|e| e.kind()), Some(std::io::ErrorKind::NotFound));
+//! ```
+//!
+//! ## Into source
+//!
+//! With `#[error_accessors]` variants with `#[error_from]` also gets `into_{variant}()`
+//! method which returns source error or error itself. If enum has single
+//! variant, or error is a struct, `into_inner()` method returns source error.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_accessors]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: closed")]
+//!     Closed,
+//! }
+//!
+//! fn read() -> std::io::Result<()> {
+//!     let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//!     match error.into_io() {
+//!         Ok(e) => Err(e),
+//!         Err(e) => Err(e.into()),
+//!     }
+//! }
+//!
+//! assert_eq!(read().unwrap_err().kind(), std::io::ErrorKind::NotFound);
+//! ```
+//!
+//! ## Kind enum
+//!
+//! Enum attribute `#[error_kind_enum]` declares `{Name}Kind` enum with
+//! same variants without fields, and implements `kind()` method.
+//! Kind implements `Copy`, `Eq` and `Hash`. Error could be compared
+//! with the kind: `error == AppErrorKind::Closed`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_kind_enum]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: closed")]
+//!     Closed,
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(error.kind(), AppErrorKind::Io);
+//! assert_eq!(AppError::Closed.kind(), AppErrorKind::Closed);
+//! assert!(error == AppErrorKind::Io);
+//! ```
+//!
+//! ## Result alias
+//!
+//! Enum attribute `#[error_result = "Name"]` declares `Result` alias
+//! with the error type. Generic parameters of the enum follow the value type:
+//! `AppResult<'a, T, P>` for `enum AppError<'a, P>`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_result = "AppResult"]
+//! enum AppError {
+//!     #[error_from("App IO: {}", 0)]
+//!     Io(std::io::Error),
+//! }
+//!
+//! fn open() -> AppResult<()> {
+//!     Err(std::io::Error::from(std::io::ErrorKind::NotFound))?
+//! }
+//!
+//! assert_eq!(open().unwrap_err().to_string().as_str(),
+//!     "App IO: entity not found");
+//! ```
+//!
+//! ## Variant name
+//!
+//! Enum attribute `#[error_variant_name]` implements method `variant_name()`
+//! which returns name of the variant.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_variant_name]
+//! enum AppError {
+//!     #[error_kind("App: closed")]
+//!     Closed,
+//! }
+//!
+//! assert_eq!(AppError::Closed.variant_name(), "Closed");
+//! ```
+//!
+//! ## Error codes
+//!
+//! Variant attribute `#[error_code = N]` defines numeric code of the error.
+//! Method `code()` returns code of the variant and const `CODES` contains
+//! list of the codes with variant names. Code should be defined for each
+//! variant and should be unique.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_code = 100]
+//!     #[error_from("App IO: {}", 0)]
+//!     Io(std::io::Error),
+//!     #[error_code = 200]
+//!     #[error_kind("App: closed")]
+//!     Closed,
+//! }
+//!
+//! assert_eq!(AppError::Closed.code(), 200);
+//! assert_eq!(AppError::CODES, &[(100, "Io"), (200, "Closed")]);
+//! ```
+//!
+//! ## Help
+//!
+//! Variant attribute `#[error_help("...")]` defines help text for the error.
+//! Method `help()` returns text of the variant. Help text printed
+//! in the alternate format `{:#}` after the error chain.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_kind("file exists")]
+//!     #[error_help("try running with --force")]
+//!     Exists,
+//! }
+//!
+//! assert_eq!(AppError::Exists.help(), Some("try running with --force"));
+//! assert_eq!(format!("{:#}", AppError::Exists).as_str(),
+//!     "App: file exists\n    Help: try running with --force");
+//! ```
+//!
+//! ## Backtrace
+//!
+//! Fields with `std::backtrace::Backtrace` type or marked with `#[error_backtrace]`
+//! captured in the `From` implementation, constructors and builders.
+//! Method `backtrace()` returns backtrace of the variant.
+//! Backtrace is captured if `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` defined.
+//!
+//! ```rust
+//! use error_rules::*;
+//! use std::backtrace::Backtrace;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from("App IO: {}", 0)]
+//!     Io(std::io::Error, Backtrace),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert!(error.backtrace().is_some());
+//! ```
+//!
+//! ## Location
+//!
+//! Field with `&'static Location<'static>` type or marked with `#[error_location]`
+//! attribute is filled with caller location. `From`, constructors and builders
+//! are marked with `#[track_caller]` so location points to the `?` operator or
+//! to the constructor call. Method `location()` returns location if variant has it.
+//! Alternate display format `{:#}` appends location to the message.
+//!
+//! ```rust
+//! use std::panic::Location;
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_constructors]
+//! enum AppError {
+//!     #[error_kind("closed {}", code)]
+//!     Closed {
+//!         code: u8,
+//!         at: &'static Location<'static>,
+//!     },
+//! }
+//!
+//! let e = AppError::closed(1);
+//! assert_eq!(e.location().unwrap().file(), file!());
+//! assert!(format!("{:#}", e).starts_with("closed 1\n    at "));
+//! ```
+//!
+//! ## Severity
+//!
+//! Variant attribute `#[error_severity(level)]` defines severity of the error.
+//! Level is one of: `trace`, `debug`, `warn`, `error`, `fatal`.
+//! Macro generates `{Enum}Severity` type and method `severity()`.
+//! Variants without attribute have `Error` level.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("connection lost")]
+//!     #[error_severity(warn)]
+//!     Reconnect,
+//!     #[error_kind("config not found")]
+//!     Config,
+//! }
+//!
+//! assert_eq!(AppError::Reconnect.severity(), AppErrorSeverity::Warn);
+//! assert_eq!(AppError::Config.severity().as_str(), "error");
+//! ```
+//!
+//! ## Syslog
+//!
+//! With the `syslog` feature enum attribute `#[error_syslog]` maps severity
+//! of the error to the syslog priority: `trace` and `debug` to `debug`,
+//! `warn` to `warning`, `error` to `err`, and `fatal` to `crit`.
+//! Method `syslog_message(facility)` returns `<PRI>` message with sources chain,
+//! `journal_fields()` returns journald fields `PRIORITY`, `MESSAGE`, `ERROR_VARIANT`
+//! and `ERROR_SOURCE` for each source error. On unix `emit_syslog(facility)`
+//! sends message to the `/dev/log` and `emit_journal()` sends fields to the journald.
+//! Attribute implements `severity()` if no variant has `#[error_severity]`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_syslog]
+//! enum AppError {
+//!     #[error_kind("connection lost")]
+//!     #[error_severity(warn)]
+//!     Reconnect,
+//! }
+//!
+//! // facility 3 is daemon
+//! assert_eq!(AppError::Reconnect.syslog_message(3).as_str(), "<28>connection lost");
+//! AppError::Reconnect.emit_journal().ok();
+//! ```
+//!
+//! ## Exit Code
+//!
+//! Variant attribute `#[error_exit_code(N)]` defines process exit code.
+//! Method `exit_code()` returns `std::process::ExitCode`.
+//! Variants without attribute have `ExitCode::FAILURE`.
+//!
+//! Enum attribute `#[error_termination]` generates `{Enum}Exit` wrapper
+//! for the `main()` result. On error it prints error with chain to stderr
+//! and exits with the mapped code.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_termination]
+//! enum AppError {
+//!     #[error_kind("invalid arguments")]
+//!     #[error_exit_code(2)]
+//!     Usage,
+//! }
+//!
+//! fn run() -> Result<(), AppError> {
+//!     Ok(())
+//! }
+//!
+//! fn main() -> AppErrorExit {
+//!     run().into()
+//! }
+//! ```
+//!
+//! ## Transient errors
+//!
+//! Variant attribute `#[error_transient]` marks variant as transient,
+//! so operation could be retried. `#[error_transient(source)]` asks the source
+//! error: it should be an enum with `is_transient()` method.
+//! Macro implements `is_transient()` method.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum DbError {
+//!     #[error_kind("connection lost")]
+//!     #[error_transient]
+//!     Connection,
+//!     #[error_kind("syntax error")]
+//!     Syntax,
+//! }
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from]
+//!     #[error_transient(source)]
+//!     Db(DbError),
+//! }
+//!
+//! assert!(AppError::from(DbError::Connection).is_transient());
+//! assert!(! AppError::from(DbError::Syntax).is_transient());
+//! ```
+//!
+//! ## Category
+//!
+//! Variant attribute `#[error_category(Name)]` defines category of the error.
+//! Macro generates `{Enum}Category` type with all defined categories
+//! and method `category()`. Variants without attribute returns `None`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("connection refused")]
+//!     #[error_category(Network)]
+//!     Refused,
+//!     #[error_kind("disk full")]
+//!     #[error_category(Storage)]
+//!     Disk,
+//! }
+//!
+//! assert_eq!(AppError::Refused.category(), Some(AppErrorCategory::Network));
+//! assert_eq!(AppError::Disk.category(), Some(AppErrorCategory::Storage));
+//! ```
+//!
+//! ## Documentation URL
+//!
+//! Variant attribute `#[error_url("...")]` defines link to the documentation
+//! or troubleshooting page. Method `url()` returns the link.
+//! Alternate display format `{:#}` appends link to the message.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: license expired")]
+//!     #[error_url("https://example.com/kb/license")]
+//!     License,
+//! }
+//!
+//! assert_eq!(AppError::License.url(), Some("https://example.com/kb/license"));
+//! assert_eq!(format!("{:#}", AppError::License).as_str(),
+//!     "App: license expired\n    See: https://example.com/kb/license");
+//! ```
+//!
+//! ## Optional source
+//!
+//! Field with `Option` type marked with `#[error_source(optional)]` attribute
+//! is a source of the error if defined. `source()` returns `None` otherwise.
+//!
+//! ```rust
+//! use std::error::Error as StdError;
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("App: task {} failed", id)]
+//!     Task {
+//!         id: u32,
+//!         #[error_source(optional)]
+//!         cause: Option<Box<dyn StdError + Send + Sync>>,
+//!     },
+//! }
+//!
+//! let error = AppError::Task { id: 1, cause: Some("timeout".into()) };
+//! assert_eq!(error.source().unwrap().to_string().as_str(), "timeout");
+//!
+//! let error = AppError::Task { id: 2, cause: None };
+//! assert!(error.source().is_none());
+//! ```
+//!
+//! `#[error_or_kind]` attribute should be defined before enum declaration.
+//! Macro implements `{Enum}OrKindExt` trait for any `Result` with an error type
+//! implemented `std::error::Error + Send + Sync + 'static`.
+//! `or_kind(variant)` and `with_kind(|| variant)` methods replace error with
+//! the given variant. Original error stored in the optional source field with
+//! `Option<Box<dyn Error>>` type, so `source()` returns it. For variants without
+//! such field original error is dropped.
+//!
+//! ```rust
+//! use std::error::Error as StdError;
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_or_kind]
+//! enum AppError {
+//!     #[error_kind("App: cache miss")]
+//!     CacheMiss {
+//!         #[error_source(optional)]
+//!         source: Option<Box<dyn StdError + Send + Sync>>,
+//!     },
+//! }
+//!
+//! fn load() -> Result<(), std::io::Error> {
+//!     Err(std::io::ErrorKind::NotFound.into())
+//! }
+//!
+//! let error = load().or_kind(AppError::CacheMiss { source: None }).unwrap_err();
+//! assert_eq!(error.to_string().as_str(), "App: cache miss");
+//! assert_eq!(error.source().unwrap().to_string().as_str(), "entity not found");
+//! ```
+//!
+//! ## Error sink
+//!
+//! `#[error_sink]` attribute should be defined before enum declaration.
+//! Macro implements `{Enum}SinkExt` trait for any `Result` with error converted
+//! into the enum. `or_report(&sender)` method returns `Some(value)` or sends
+//! error into `std::sync::mpsc::Sender` and returns `None`. It helps to collect
+//! errors of the background tasks in one place.
+//!
+//! ```rust
+//! use std::sync::mpsc;
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_sink]
+//! enum AppError {
+//!     #[error_from("App: {}", 0)]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let (sink, errors) = mpsc::channel();
+//!
+//! let sink2 = sink.clone();
+//! std::thread::spawn(move || {
+//!     let r: Result<(), std::io::Error> = Err(std::io::ErrorKind::NotFound.into());
+//!     assert!(r.or_report(&sink2).is_none());
+//! }).join().unwrap();
+//!
+//! let error: AppError = errors.recv().unwrap();
+//! assert_eq!(error.to_string().as_str(), "App: entity not found");
+//! ```
+//!
+//! ## Conditional variants
+//!
+//! `#[cfg]` attributes of the variant are copied to the generated code:
+//! `Display` and `source()` arms, `From` conversions, constructors, builders
+//! and other methods. So variant could be enabled with crate feature.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[cfg(feature = "postgres")]
+//!     #[error_from]
+//!     Postgres(postgres::Error),
+//! }
+//! ```
+//!
+//! ## PartialEq
+//!
+//! `#[error_eq]` attribute implements `PartialEq` with `eq_kind()` method:
+//! variants and payload fields are compared, wrapped source errors are ignored.
+//! It helps to use `assert_eq!` in tests even if source error is not comparable.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_eq]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: code {}", 0)]
+//!     Code(u16),
+//! }
+//!
+//! let e1: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! let e2: AppError = std::io::Error::from(std::io::ErrorKind::Other).into();
+//! assert_eq!(e1, e2);
+//! assert_ne!(AppError::Code(404), AppError::Code(500));
+//! ```
+//!
+//! `#[error_eq(Eq)]` implements `Eq` too. Compared payload fields should implement `Eq`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_eq(Eq)]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("App: code {}", 0)]
+//!     Code(u16),
+//! }
+//!
+//! fn assert_eq_total<T: Eq>(_: &T) {}
+//! assert_eq_total(&AppError::Code(404));
+//! ```
+//!
+//! ## Clone
+//!
+//! Most of errors could not be cloned, for example `std::io::Error`.
+//! `#[error_clone]` attribute should be defined before `#[derive(Error)]`.
+//! It changes type of the source field in the `#[error_from]` and
+//! `#[error_transparent]` variants or structs from `T` into `std::sync::Arc<T>`
+//! and derives `Clone` for the error. `#[error_source(optional)]` fields
+//! changed from `Option<T>` into `Option<Arc<T>>`.
+//! `From<T>` wraps error into `Arc`, method `source()` returns the inner error.
+//! Other fields should implement `Clone`. Attribute requires `std`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[error_clone]
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! let error2 = error.clone();
+//! assert_eq!(error2.to_string().as_str(), "App: entity not found");
+//! ```
+//!
+//! ## Serialize
+//!
+//! With the `serde` feature `#[error_serialize]` attribute implements
+//! `serde::Serialize` for errors. Crate should depend on the `serde`.
+//! Error serialized as a struct with
+//! variant name, message, code if `#[error_code]` defined, and list of the
+//! source errors messages.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_serialize]
+//! enum AppError {
+//!     #[error_from]
+//!     #[error_code = 100]
+//!     Io(std::io::Error),
+//! }
+//!
+//! let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+//! assert_eq!(serde_json::to_string(&error).unwrap().as_str(),
+//!     r#"{"variant":"Io","message":"entity not found","code":100,"chain":["entity not found"]}"#);
+//! ```
+//!
+//! ## Deserialize
+//!
+//! With the `serde` feature `#[error_deserialize]` attribute implements
+//! `serde::Deserialize` and `serde::Serialize` for errors without source errors.
+//! Serialized error contains variant fields, so error could be restored
+//! on the other side. Crate should depend on the `serde` with `derive` feature.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_deserialize]
+//! enum RpcError {
+//!     #[error_kind("not found: {}", 0)]
+//!     NotFound(String),
+//!     #[error_kind("busy")]
+//!     Busy,
+//! }
+//!
+//! let text = serde_json::to_string(&RpcError::NotFound("a.txt".to_owned())).unwrap();
+//! let error: RpcError = serde_json::from_str(&text).unwrap();
+//! assert_eq!(error.to_string().as_str(), "not found: a.txt");
+//! ```
+//!
+//! ## Wire format
+//!
+//! Variant attribute `#[error_wire(rename = "...", since = "...")]` controls
+//! serialized error for the `#[error_serialize]` and `#[error_deserialize]`.
+//! `rename` defines variant tag independent of the Rust identifier.
+//! `#[error_deserialize]` accepts the variant name as well, so payloads
+//! of the previous versions could be parsed. `since` defines version of the wire
+//! format where variant appeared. Method `wire_since()` returns the version
+//! or `None` for variants without it.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_deserialize]
+//! enum RpcError {
+//!     #[error_kind("not found")]
+//!     #[error_wire(rename = "NOT_FOUND", since = "2")]
+//!     NotFound,
+//! }
+//!
+//! let text = serde_json::to_string(&RpcError::NotFound).unwrap();
+//! assert!(text.starts_with(r#"{"variant":"NOT_FOUND","#));
+//! let error: RpcError = serde_json::from_str(r#"{"variant":"NotFound"}"#).unwrap();
+//! assert_eq!(error.wire_since(), Some("2"));
+//! ```
+//!
+//! ## Miette
+//!
+//! With the `miette` feature enum attribute `#[error_diagnostic]` implements
+//! `miette::Diagnostic` for the error.
+//! Values of the `#[error_code]`, `#[error_help]` and `#[error_url]` attributes
+//! returned with `code()`, `help()` and `url()` methods of the `Diagnostic`.
+//! Crate should depend on the `miette`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_diagnostic]
+//! enum AppError {
+//!     #[error_kind("config not found")]
+//!     #[error_code = 100]
+//!     #[error_help("create config with --init")]
+//!     #[error_url("https://example.com/kb/config")]
+//!     Config,
+//! }
+//!
+//! fn main() -> miette::Result<()> {
+//!     Err(AppError::Config)?
+//! }
+//! ```
+//!
+//! ## Anyhow
+//!
+//! `anyhow::Error` does not implement `std::error::Error`.
+//! `#[error_from]` accepts `anyhow::Error` or any other type with `Deref`
+//! to the `dyn Error + Send + Sync`: macro implements `From<anyhow::Error>`
+//! and `source()` returns the error wrapped into `anyhow::Error`.
+//! Type resolved by the compiler, so aliases like `use anyhow::Error` work too.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from]
+//!     Other(anyhow::Error),
+//! }
+//!
+//! let error: AppError = anyhow::anyhow!("failed").into();
+//! assert_eq!(error.to_string().as_str(), "App: failed");
+//! ```
+//!
+//! ## Eyre
+//!
+//! `#[error_from]` accepts `eyre::Report` the same way as `anyhow::Error`:
+//! macro implements `From<eyre::Report>` and `source()` returns the error
+//! wrapped into the report. Conversion from the error into `eyre::Report`
+//! implemented by `eyre` itself, chain of the error is preserved.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_from]
+//!     Other(eyre::Report),
+//! }
+//!
+//! fn run() -> eyre::Result<()> {
+//!     Err(AppError::from(eyre::eyre!("failed")))?
+//! }
+//! ```
+//!
+//! ## Tracing
+//!
+//! `#[error_trace(level = "warn")]` attribute should be defined before enum
+//! declaration. `From` conversions, constructors and builders emit `tracing`
+//! event with variant name, message and location where error was created.
+//! Level is one of: `trace`, `debug`, `info`, `warn`, `error`.
+//! Default level is `error`. Crate should depend on the `tracing`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_trace(level = "warn")]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//! }
+//!
+//! fn open(path: &str) -> Result<std::fs::File, AppError> {
+//!     // emits event: WARN variant="Io" location=src/main.rs:10:5 No such file
+//!     Ok(std::fs::File::open(path)?)
+//! }
+//! ```
+//!
+//! ## Log
+//!
+//! With the `log` feature enum attribute `#[error_log]` generates
+//! `{Enum}ResultExt` trait for the `Result` with the error. Method `log_err(level)`
+//! logs error with chain and returns result unchanged. Method `log_warn()` logs
+//! with `Warn` level.
+//! Crate should depend on the `log`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_log]
+//! #[error_prefix = "App"]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//! }
+//!
+//! fn load(path: &str) -> Result<String, AppError> {
+//!     Ok(std::fs::read_to_string(path)?)
+//! }
+//!
+//! let config = load("app.conf").log_warn().unwrap_or_default();
+//! ```
+//!
+//! ## Error stack
+//!
+//! With the `error-stack` feature enum attribute `#[error_report]` implements
+//! `into_report()` method.
+//! It converts error into `error_stack::Report` with attachments: code from the
+//! `#[error_code]`, help from the `#[error_help]` and link from the `#[error_url]`.
+//! Crate should depend on the `error-stack`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_report]
+//! enum AppError {
+//!     #[error_kind("config not found")]
+//!     #[error_code = 100]
+//!     #[error_help("create config with --init")]
+//!     Config,
+//! }
+//!
+//! fn run() -> error_stack::Result<(), AppError> {
+//!     Err(AppError::Config.into_report())
+//! }
+//! ```
+//!
+//! ## no_std
+//!
+//! `#[error_no_std]` attribute should be defined before enum declaration.
+//! Macro implements `core::error::Error` instead of `std::error::Error`,
+//! other implementations are same. `From<Error> for std::io::Error` and
+//! integrations with the external crates are not implemented.
+//! Attributes required `std` (`error_snapshot`, `error_fingerprint`, `error_truncate`,
+//! `error_termination`, `error_exit_code`, `error_ffi`, `error_io_class`, `error_io_kind`,
+//! `error_into_io`, `error_serialize`, `error_deserialize`, `error_diagnostic`, `error_log`,
+//! `error_or_kind`, `error_sink`, `error_examples`, `error_report`, `error_wasm`,
+//! `error_axum`, `error_problem_json`, `error_crash_report`, `error_syslog`, `error_grpc`,
+//! `error_py`)
+//! and backtrace fields are compile errors.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_no_std]
+//! #[error_prefix = "Sensor"]
+//! enum SensorError {
+//!     #[error_from]
+//!     Utf8(core::str::Utf8Error),
+//!     #[error_kind("timeout {}ms", 0)]
+//!     Timeout(u32),
+//! }
+//!
+//! assert_eq!(SensorError::Timeout(10).to_string().as_str(), "Sensor: timeout 10ms");
+//! ```
+//!
+//! ## WebAssembly
+//!
+//! With the `wasm` feature enum attribute `#[error_wasm]` implements
+//! `From<Error> for wasm_bindgen::JsValue`.
+//! Error converted into object with `variant`, `message`, `code` if
+//! `#[error_code]` defined, and `chain` with messages of the source errors.
+//! Crate should depend on the `wasm-bindgen` and `js-sys`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_wasm]
+//! enum AppError {
+//!     #[error_kind("invalid input")]
+//!     #[error_code = 400]
+//!     Input,
+//! }
+//!
+//! #[wasm_bindgen]
+//! pub fn parse(text: &str) -> Result<u32, JsValue> {
+//!     text.parse().map_err(|_| AppError::Input.into())
+//! }
+//! ```
+//!
+//! ## FFI
+//!
+//! `#[error_ffi]` attribute should be defined before enum declaration.
+//! Each variant should have `#[error_code = N]`. Macro generates `#[repr(C)]`
+//! enum `{Enum}Code` with codes of the variants, method `ffi_code()`
+//! and method `to_ffi()` which returns code and message as `CString`.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! #[error_ffi]
+//! enum AppError {
+//!     #[error_kind("not found")]
+//!     #[error_code = 404]
+//!     NotFound,
+//! }
+//!
+//! let (code, message) = AppError::NotFound.to_ffi();
+//! assert_eq!(code, AppErrorCode::NotFound as i32);
+//! assert_eq!(message.to_str().unwrap(), "not found");
+//! ```
+//!
+//! ## IO error kind
+//!
+//! Error could be converted into `std::io::Error`, for example to return it
+//! from the `Read` or `Write` implementation. Kind of the `std::io::Error` is
+//! `Other` by default. Variants with the source error keep kind of the source
+//! if it is `std::io::Error`, checked with `downcast_ref()`, so `Box<io::Error>`
+//! and `Arc<io::Error>` sources keep kind too.
+//! Variant attribute `#[error_io_kind(Kind)]` defines kind for the variant.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_from]
+//!     Io(std::io::Error),
+//!     #[error_kind("config not found")]
+//!     #[error_io_kind(NotFound)]
+//!     Config,
+//! }
+//!
+//! let error: std::io::Error = AppError::Config.into();
+//! assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+//! ```
+//!
+//! ## HTTP status
+//!
+//! Variant attribute `#[error_status(N)]` defines HTTP status of the error.
+//! Method `status()` returns status of the variant or `500` if not defined.
+//! With the `problem-json` feature enum attribute `#[error_problem_json]` implements
+//! method `problem_json(instance)` which returns RFC 7807 problem document.
+//! `type` is a link from the `#[error_url]` or `about:blank`, `title` is a variant name,
+//! `detail` is an error message. `code` is added if `#[error_code]` defined.
+//! Methods are not implemented for enums without attributes, so enabled feature
+//! does not change errors of the other crates.
+//!
+//! ```rust
+//! use error_rules::*;
+//!
+//! #[derive(Debug, Error)]
+//! enum AppError {
+//!     #[error_kind("user not found")]
+//!     #[error_status(404)]
+//!     NotFound,
+//!     #[error_kind("internal error")]
+//!     Internal,
+//! }
+//!
+//! assert_eq!(AppError::NotFound.status(), 404);
+//! assert_eq!(AppError::Internal.status(), 500);
+//! ```
+//!
+//! ## Axum
+//!
+//! With the `axum` feature enum attribute `#[error_axum]` implements
+//! `axum::response::IntoResponse`.
+//! Response has status from the `#[error_status(N)]` and RFC 7807 problem document
+//! in the body. Feature enables `problem-json`, attribute implements `problem_json()`.
+//! Crate should depend on the `axum`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_axum]
+//! enum AppError {
+//!     #[error_kind("user not found")]
+//!     #[error_status(404)]
+//!     NotFound,
+//! }
+//!
+//! async fn user(Path(id): Path<u32>) -> Result<Json<User>, AppError> {
+//!     find_user(id).map(Json).ok_or(AppError::NotFound)
+//! }
+//! ```
+//!
+//! ## Tonic
+//!
+//! With the `tonic` feature enum attribute `#[error_grpc]` implements
+//! `From<Error> for tonic::Status`. Variant attribute `#[error_grpc(Code)]`
+//! defines gRPC code of the variant. Code of other variants is `Unknown` or
+//! defined in the enum attribute: `#[error_grpc(Internal)]`.
+//! Message of the status is an error message.
+//! Crate should depend on the `tonic`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_grpc]
+//! enum AppError {
+//!     #[error_kind("user {} not found", 0)]
+//!     #[error_grpc(NotFound)]
+//!     NotFound(u32),
+//! }
+//!
+//! let status: tonic::Status = AppError::NotFound(1).into();
+//! assert_eq!(status.code(), tonic::Code::NotFound);
+//! assert_eq!(status.message(), "user 1 not found");
+//! ```
+//!
+//! ## PyO3
+//!
+//! With the `pyo3` feature enum attribute `#[error_py]` implements
+//! `From<Error> for pyo3::PyErr`.
+//! Variant attribute `#[error_py(Exception)]` defines Python exception of the variant.
+//! Single name is a builtin exception from the `pyo3::exceptions`, for example
+//! `ValueError` is a `PyValueError`. Path used for custom exceptions.
+//! Enum attribute `#[error_py(Category = "Exception", ...)]` defines exceptions
+//! for the variants with `#[error_category]`, and `#[error_py(Exception)]`
+//! defines exception for other variants. By default other variants raise
+//! `RuntimeError`. Crate should depend on the `pyo3`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_py(Network = "ConnectionError")]
+//! enum AppError {
+//!     #[error_kind("invalid port: {}", 0)]
+//!     #[error_py(ValueError)]
+//!     Port(u32),
+//!     #[error_kind("connection refused")]
+//!     #[error_category(Network)]
+//!     Refused,
+//! }
+//!
+//! #[pyfunction]
+//! fn connect(port: u32) -> PyResult<()> {
+//!     Err(AppError::Port(port).into())
+//! }
+//! ```
+//!
+//! ## Defmt
+//!
+//! With the `defmt` feature enum attribute `#[error_defmt]` implements
+//! `defmt::Format` for embedded targets.
+//! Format string of the `#[error_kind]` is interned by `defmt::write!`, fields
+//! should implement `defmt::Format`. Expressions in arguments are not supported,
+//! variants with expressions and variants without `#[error_kind]` are formatted
+//! as a variant name. Crate should depend on the `defmt`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, Error)]
+//! #[error_no_std]
+//! #[error_defmt]
+//! #[error_prefix = "Sensor"]
+//! enum SensorError {
+//!     #[error_kind("timeout on channel {}", 0)]
+//!     Timeout(u8),
+//! }
+//!
+//! defmt::error!("{}", SensorError::Timeout(1));
+//! ```
+
+extern crate proc_macro;
+
+mod error_clone;
+mod map_errors;
+mod merge_errors;
+
+use proc_macro2::{TokenStream, Span, Ident};
+use quote::{quote, ToTokens};
+use syn::{
+    self,
+    parse_macro_input,
+};
+
+
+/// Checks placeholders of the format string against defined arguments,
+/// or against variant fields if arguments are not defined
+fn check_fmt_args(meta_list: &syn::MetaList, fields: &syn::Fields) -> syn::Result<()> {
+    let fmt_lit = &meta_list.nested[0];
+    let fmt = match fmt_lit {
+        syn::NestedMeta::Lit(syn::Lit::Str(v)) => v.value(),
+        _ => return Ok(()),
+    };
+
+    let arg_count = meta_list.nested.len() - 1;
+    let mut used = vec![false; arg_count];
+    let mut next = 0;
+
+    for arg in fmt_args(&fmt) {
+        let index = if arg.is_empty() {
+            next += 1;
+            next - 1
+        } else if let Ok(v) = arg.parse::<usize>() {
+            v
+        } else {
+            // `{source}` bound by `#[error_from]` to the source field
+            let found = arg == "source" || fields.iter().any(|v| matches!(&v.ident, Some(v) if v == arg.as_str()));
+            if arg_count == 0 && ! found {
+                return Err(syn::Error::new_spanned(fmt_lit,
+                    format!("field {} not found", arg)))
+            }
+            continue
+        };
+
+        if arg_count == 0 {
+            if arg.is_empty() {
+                return Err(syn::Error::new_spanned(fmt_lit,
+                    "format string has placeholder {} but arguments are not defined"))
+            }
+            if index >= fields.len() {
+                return Err(syn::Error::new_spanned(fmt_lit,
+                    format!("field index {} out of range, variant has {} fields", index, fields.len())))
+            }
+            continue
+        }
+
+        if index >= arg_count {
+            return Err(syn::Error::new_spanned(fmt_lit,
+                format!("format string requires {} arguments but {} defined", index + 1, arg_count)))
+        }
+        used[index] = true;
+    }
+
+    match used.iter().position(|v| ! v) {
+        Some(i) => Err(syn::Error::new_spanned(&meta_list.nested[i + 1],
+            "argument never used in format string")),
+        None => Ok(()),
+    }
+}
+
+
+/// Names of the arguments in the format string: `{name}`, `{0:?}`,
+/// and width or precision parameters like `{:>width$}`.
+/// Precision `{:.*}` takes next positional argument before the value
+fn fmt_args(fmt: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue
+        }
+
+        let arg: String = chars.by_ref()
+            .take_while(|&c| c != '}')
+            .collect();
+        let mut arg = arg.splitn(2, ':');
+        let name = arg.next().unwrap_or_default().trim().to_owned();
+        let spec = arg.next().unwrap_or_default();
+        if spec.contains(".*") {
+            result.push(String::new());
+        }
+        result.push(name);
+
+        let mut param = String::new();
+        for c in spec.chars() {
+            if c.is_alphanumeric() || c == '_' {
+                param.push(c);
+            } else {
+                if c == '$' && ! param.is_empty() {
+                    result.push(param.clone());
+                }
+                param.clear();
+            }
+        }
+    }
+
+    result
+}
+
+
+/// Converts `#[error_kind]` format into the `defmt` format with list of the arguments.
+/// Returns `None` for expressions and adapters
+fn defmt_fmt(meta_list: &syn::MetaList, fields: &syn::Fields) -> Option<(String, Vec<Ident>)> {
+    let field_id = |index: usize| match fields.iter().nth(index) {
+        Some(syn::Field { ident: Some(v), .. }) => Some(v.clone()),
+        Some(_) => Some(Ident::new(&format!("i{}", index), Span::call_site())),
+        None => None,
+    };
+
+    let mut attr_list = Vec::new();
+    for attr in meta_list.nested.iter().skip(1) {
+        let attr_id = match attr {
+            syn::NestedMeta::Lit(syn::Lit::Int(v)) => field_id(v.base10_parse().ok()?)?,
+            syn::NestedMeta::Meta(syn::Meta::Path(v)) => v.get_ident()?.clone(),
+            _ => return None,
+        };
+        attr_list.push(attr_id);
+    }
+
+    let fmt = match &meta_list.nested[0] {
+        syn::NestedMeta::Lit(syn::Lit::Str(v)) => v.value(),
+        _ => return None,
+    };
+
+    let mut result = String::new();
+    let mut arg_list = Vec::new();
+    let mut chars = fmt.chars().peekable();
+    let mut position = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push_str("{{");
+                continue
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push_str("}}");
+                continue
+            }
+            '{' => {},
+            c => {
+                result.push(c);
+                continue
+            }
+        }
+
+        let arg: String = chars.by_ref()
+            .take_while(|&c| c != '}')
+            .collect();
+        let mut arg = arg.splitn(2, ':');
+        let name = arg.next().unwrap_or_default().trim();
+        let spec = arg.next().unwrap_or_default();
+
+        let arg_id = if name.is_empty() {
+            position += 1;
+            attr_list.get(position - 1)?.clone()
+        } else if let Ok(index) = name.parse::<usize>() {
+            if attr_list.is_empty() {
+                field_id(index)?
+            } else {
+                attr_list.get(index)?.clone()
+            }
+        } else {
+            fields.iter().find_map(|v| v.ident.as_ref().filter(|v| *v == name))?.clone()
+        };
+        arg_list.push(arg_id);
+
+        match spec.chars().last() {
+            Some(c) if "?xXbo".contains(c) => result.push_str(&format!("{{:{}}}", c)),
+            _ => result.push_str("{}"),
+        }
+    }
+
+    Some((result, arg_list))
+}
+
+
+/// Checks if format string contains named argument `{name}`
+fn fmt_has_named(fmt: &str, name: &str) -> bool {
+    fmt_args(fmt).iter().any(|v| v == name)
+}
+
+
+/// Replaces inline positions `{0}` and `{:1$}` with field bindings `{i0}` and `{:i1$}`
+fn fmt_inline_positions(fmt: &str) -> String {
+    let mut result = String::new();
+    let mut chars = fmt.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        result.push(c);
+        if c != '{' {
+            continue
+        }
+        if chars.peek() == Some(&'{') {
+            result.push(chars.next().unwrap());
+            continue
+        }
+
+        let arg: String = chars.by_ref()
+            .take_while(|&c| c != '}')
+            .collect();
+
+        let mut param = String::new();
+        for c in arg.chars().chain(std::iter::once('}')) {
+            if c.is_alphanumeric() || c == '_' {
+                param.push(c);
+                continue
+            }
+            let is_position = param.chars().all(|c| c.is_ascii_digit());
+            let is_first = result.ends_with('{');
+            if ! param.is_empty() && is_position && (is_first || c == '$') {
+                result.push('i');
+            }
+            result.push_str(&param);
+            result.push(c);
+            param.clear();
+        }
+    }
+
+    result
+}
+
+
+/// Checks if type is named `Path` or `PathBuf`, `std::path` types resolved on display
+fn is_path(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(v) => {
+            let ident = &v.path.segments.last().unwrap().ident;
+            ident == "Path" || ident == "PathBuf"
+        }
+        syn::Type::Reference(v) => is_path(&v.elem),
+        _ => false,
+    }
+}
+
+
+/// Number of references before the type
+fn ref_depth(ty: &syn::Type) -> usize {
+    match ty {
+        syn::Type::Reference(v) => 1 + ref_depth(&v.elem),
+        _ => 0,
+    }
+}
+
+
+/// First type argument, `T` for `Option<T>`
+fn type_arg(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(v) => v.path.segments.last()?,
+        _ => return None,
+    };
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(v) => match v.args.first() {
+            Some(syn::GenericArgument::Type(v)) => Some(v),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+
+/// Checks if type is `Box<dyn Trait>`
+fn is_boxed_dyn(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(v) if v.path.segments.last().unwrap().ident == "Box" => {
+            matches!(type_arg(ty), Some(syn::Type::TraitObject(_)))
+        }
+        _ => false,
+    }
+}
+
+
+/// Checks if type is `Box<T>` or `Arc<T>`
+fn is_pointer(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(v) => {
+            let ident = &v.path.segments.last().unwrap().ident;
+            ident == "Box" || ident == "Arc"
+        }
+        _ => false,
+    }
+}
+
+
+/// Inner type of the `Arc<T>` if it is not a trait object
+fn arc_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(v) => v.path.segments.last().unwrap(),
+        _ => return None,
+    };
+    if segment.ident != "Arc" {
+        return None
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(v) => match v.args.first() {
+            Some(syn::GenericArgument::Type(syn::Type::TraitObject(_))) => None,
+            Some(syn::GenericArgument::Type(v)) => Some(v),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+
+/// Checks if type is `Box<dyn Fn(&mut Formatter) -> fmt::Result>`
+fn is_lazy_display(ty: &syn::Type) -> bool {
+    let path = match ty {
+        syn::Type::Path(v) => &v.path,
+        _ => return false,
+    };
+
+    let segment = path.segments.last().unwrap();
+    if segment.ident != "Box" {
+        return false
+    }
+
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(v) => &v.args,
+        _ => return false,
+    };
+
+    if let Some(syn::GenericArgument::Type(syn::Type::TraitObject(v))) = args.first() {
+        v.bounds.iter().any(|v| match v {
+            syn::TypeParamBound::Trait(v) => {
+                let segment = v.path.segments.last().unwrap();
+                segment.ident == "Fn" &&
+                    matches!(segment.arguments, syn::PathArguments::Parenthesized(_))
+            }
+            _ => false,
+        })
+    } else {
+        false
+    }
+}
+
+
+/// Field referenced in the display attributes:
+/// number of the unnamed field or name of the named field
+fn parse_field_ref<'a>(attr: &syn::NestedMeta, fields: &'a syn::Fields) -> syn::Result<(Ident, Option<&'a syn::Field>)> {
+    match attr {
+        syn::NestedMeta::Lit(syn::Lit::Int(v)) => {
+            let attr = v.base10_parse::<usize>()?;
+            let attr_id = Ident::new(&format!("i{}", attr), Span::call_site());
+            Ok((attr_id, fields.iter().nth(attr)))
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(v)) => {
+            let attr_id = match v.get_ident() {
+                Some(v) => v.clone(),
+                None => return Err(syn::Error::new_spanned(v, "attributes should be number or field name")),
+            };
+            match fields.iter().find(|v| v.ident.as_ref() == Some(&attr_id)) {
+                Some(field) => Ok((attr_id, Some(field))),
+                None => Err(syn::Error::new_spanned(v, format!("field {} not found", attr_id))),
+            }
+        }
+        _ => Err(syn::Error::new_spanned(attr, "attributes should be number or field name")),
+    }
+}
+
+
+/// Checks if field has `Backtrace` type or marked with `#[error_backtrace]`
+fn is_backtrace(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|v| v.path.is_ident("error_backtrace")) || match &field.ty {
+        syn::Type::Path(v) => v.path.segments.last().unwrap().ident == "Backtrace",
+        _ => false,
+    }
+}
+
+
+/// Text from `#[attr("...")]` or `#[attr = "..."]`
+fn parse_text(meta: &syn::Meta) -> syn::Result<String> {
+    match meta {
+        syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+            syn::NestedMeta::Lit(syn::Lit::Str(v)) => Ok(v.value()),
+            _ => Err(syn::Error::new_spanned(meta, "meta format mismatch")),
+        },
+        syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(v), .. }) => Ok(v.value()),
+        _ => Err(syn::Error::new_spanned(meta, "meta format mismatch")),
+    }
+}
+
+
+/// Checks if field marked with `#[error_source(optional)]`
+fn is_optional_source(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        attr.path.is_ident("error_source") && match attr.parse_meta() {
+            Ok(syn::Meta::List(v)) => v.nested.iter().any(|v| matches!(v,
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("optional"))),
+            _ => false,
+        }
+    })
+}
+
+
+/// Variant of the `std::io::ErrorKind` from `#[error_io_kind(Kind)]`
+/// or `tonic::Code` from `#[error_grpc(Code)]`
+fn parse_kind(meta: &syn::Meta) -> syn::Result<Ident> {
+    match meta {
+        syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+            syn::NestedMeta::Meta(syn::Meta::Path(v)) => match v.get_ident() {
+                Some(v) => Ok(v.clone()),
+                None => Err(syn::Error::new_spanned(v, "meta format mismatch")),
+            },
+            _ => Err(syn::Error::new_spanned(meta, "meta format mismatch")),
+        },
+        _ => Err(syn::Error::new_spanned(meta, "meta format mismatch")),
+    }
+}
+
+
+/// Python exception from `#[error_py(Exception)]`.
+/// Single name is a builtin exception from the `pyo3::exceptions`
+fn py_exception(path: &syn::Path) -> TokenStream {
+    match path.get_ident() {
+        Some(v) => {
+            let ident = Ident::new(&format!("Py{}", v), v.span());
+            quote! { pyo3::exceptions::#ident }
+        }
+        None => quote! { #path },
+    }
+}
+
+
+/// Checks if field has `&Location` type or marked with `#[error_location]`
+fn is_location(field: &syn::Field) -> bool {
+    field.attrs.iter().any(|v| v.path.is_ident("error_location")) || match &field.ty {
+        syn::Type::Reference(v) => match v.elem.as_ref() {
+            syn::Type::Path(v) => v.path.segments.last().unwrap().ident == "Location",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+
+/// `#[track_caller]` for functions which creates variant with location field
+fn impl_track_caller(fields: &syn::Fields) -> TokenStream {
+    if fields.iter().any(is_location) {
+        quote! { #[track_caller] }
+    } else {
+        TokenStream::new()
+    }
+}
+
+
+/// Expression to fill the field automatically in the generated `From`,
+/// constructors and builders
+fn field_capture(field: &syn::Field) -> Option<TokenStream> {
+    if is_backtrace(field) {
+        return Some(quote! { ::std::backtrace::Backtrace::capture() })
+    }
+    if is_location(field) {
+        return Some(quote! { ::core::panic::Location::caller() })
+    }
+
+    None
+}
+
+
+/// Field used as error source: the only payload field, field marked with `#[error_source]`
+/// or field named `source`
+fn source_field(fields: &syn::Fields) -> syn::Result<(usize, &syn::Field)> {
+    let mut payload = fields.iter().enumerate().filter(|(_, v)| field_capture(v).is_none());
+    if let (Some(v), None) = (payload.next(), payload.next()) {
+        return Ok(v)
+    }
+
+    let mut marked = fields.iter().enumerate()
+        .filter(|(_, v)| v.attrs.iter().any(|a| a.path.is_ident("error_source")));
+    match (marked.next(), marked.next()) {
+        (Some(v), None) => return Ok(v),
+        (Some(_), Some((_, v))) => {
+            let message = "only one field could be marked with #[error_source]";
+            return Err(syn::Error::new_spanned(v, message))
+        }
+        _ => {},
+    }
+
+    match fields.iter().enumerate().find(|(_, v)| matches!(&v.ident, Some(v) if v == "source")) {
+        Some(v) => Ok(v),
+        None => {
+            let message = "variant with multiple fields should mark source with #[error_source]";
+            Err(syn::Error::new_spanned(fields, message))
+        }
+    }
+}
+
+
+/// Expression from `expr("...")` with receiver bound to the variant field
+fn impl_display_expr(meta_list: &syn::MetaList,
+    fields: &syn::Fields,
+    used_list: &mut Vec<Ident>) -> syn::Result<TokenStream>
+{
+    let mut expr = match meta_list.nested.first() {
+        Some(syn::NestedMeta::Lit(syn::Lit::Str(v))) => v.parse::<syn::Expr>()?,
+        _ => return Err(syn::Error::new_spanned(meta_list, "expr attribute should be string")),
+    };
+
+    let receiver = expr_receiver(&mut expr);
+    match receiver {
+        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(v), .. }) => {
+            let attr = v.base10_parse::<usize>()?;
+            let attr_id = Ident::new(&format!("i{}", attr), Span::call_site());
+            *receiver = syn::parse_quote!(#attr_id);
+        }
+        syn::Expr::Path(v) => {
+            let field = v.path.get_ident()
+                .and_then(|id| fields.iter().find(|v| v.ident.as_ref() == Some(id)));
+            if let Some(field) = field {
+                let attr_id = field.ident.clone().unwrap();
+                if ! used_list.contains(&attr_id) {
+                    used_list.push(attr_id);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Ok(quote! { #expr })
+}
+
+
+/// Type name without extra spaces between tokens
+fn type_to_string(ty: &syn::Type) -> String {
+    quote! { #ty }.to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" < ", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
+
+/// Limit of the error text length from `#[error_truncate(N)]`
+fn parse_truncate(meta: &syn::Meta) -> syn::Result<usize> {
+    if let syn::Meta::List(v) = meta {
+        if v.nested.len() == 1 {
+            if let syn::NestedMeta::Lit(syn::Lit::Int(v)) = &v.nested[0] {
+                return v.base10_parse::<usize>()
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(meta, "meta format mismatch"))
+}
+
+
+/// Limit of the field text length from `#[error_truncate(N)]` on the field.
+/// Attribute format is checked in the `impl_variant`
+fn field_truncate(field: &syn::Field) -> Option<usize> {
+    field.attrs.iter()
+        .find(|v| v.path.is_ident("error_truncate"))
+        .and_then(|v| v.parse_meta().and_then(|v| parse_truncate(&v)).ok())
+}
+
+
+/// Converts variant name from CamelCase to snake_case.
+/// Acronyms kept as one word: `IO` into `io`, `HTTPError` into `http_error`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = if i != 0 { Some(chars[i - 1]) } else { None };
+            let next = chars.get(i + 1);
+            let is_word = match prev {
+                Some(p) if p.is_uppercase() => matches!(next, Some(n) if n.is_lowercase()),
+                Some(_) => true,
+                None => false,
+            };
+            if is_word {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+
+/// Method name. Keywords used as raw identifiers
+fn method_ident(name: &str) -> Ident {
+    const KEYWORD_LIST: &[&str] = &[
+        "as", "async", "await", "break", "const", "continue", "dyn", "else",
+        "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let",
+        "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+        "static", "struct", "trait", "true", "type", "unsafe", "use",
+        "where", "while", "abstract", "become", "box", "do", "final",
+        "macro", "override", "priv", "try", "typeof", "unsized", "virtual",
+        "yield",
+    ];
+
+    match name {
+        "self" | "super" | "crate" => Ident::new(&format!("{}_", name), Span::call_site()),
+        name if KEYWORD_LIST.contains(&name) => Ident::new_raw(name, Span::call_site()),
+        name => Ident::new(name, Span::call_site()),
+    }
+}
+
+
+/// Options of the `#[error_from(...)]` attribute
+#[derive(Default)]
+struct FromOptions {
+    /// conversion function from `with = "path"`
+    with: Option<syn::Path>,
+    /// input type of the conversion function from `from = "Type"`
+    from: Option<syn::Type>,
+    /// `#[error_transparent]` forwards `source()` to the inner error
+    transparent: bool,
+    /// `no_from` skips `From` implementation
+    no_from: bool,
+    /// `primary` skips `From` implementation for other variants with same source type
+    primary: bool,
+    /// `default` fills other fields with `Default::default()` in the `From` implementation
+    default: bool,
+}
+
+
+/// Splits `key = "value"` options from the display items of `#[error_from(...)]`
+fn parse_from_options(meta_list: &syn::MetaList) -> syn::Result<(FromOptions, syn::MetaList)> {
+    let mut options = FromOptions::default();
+    let mut display = meta_list.clone();
+    display.nested.clear();
+
+    for attr in meta_list.nested.iter() {
+        match attr {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(v)) => {
+                let value = match &v.lit {
+                    syn::Lit::Str(v) => v,
+                    lit => return Err(syn::Error::new_spanned(lit, "option value should be string")),
+                };
+                if v.path.is_ident("with") {
+                    options.with = Some(value.parse()?);
+                } else if v.path.is_ident("from") {
+                    options.from = Some(value.parse()?);
+                } else {
+                    return Err(syn::Error::new_spanned(&v.path, "unknown option"))
+                }
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("no_from") => {
+                options.no_from = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("primary") => {
+                options.primary = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("default") => {
+                options.default = true;
+            }
+            attr => display.nested.push(attr.clone()),
+        }
+    }
+
+    if options.with.is_some() != options.from.is_some() {
+        let message = "with and from options should be defined together";
+        return Err(syn::Error::new_spanned(meta_list, message))
+    }
+
+    Ok((options, display))
+}
+
+
+/// Parses display attribute. Arguments which could not be parsed as meta,
+/// like `0.kind()` or `path.display()`, wrapped into `expr("...")`
+fn parse_display_meta(attr: &syn::Attribute) -> syn::Result<syn::Meta> {
+    if let Ok(meta) = attr.parse_meta() {
+        return Ok(meta)
+    }
+
+    let arg_list = attr.parse_args_with(
+        syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated
+    )?;
+
+    let nested = arg_list.iter().map(|expr| {
+        match syn::parse2::<syn::NestedMeta>(quote! { #expr }) {
+            Ok(v) => v,
+            Err(_) => {
+                let text = quote! { #expr }.to_string();
+                syn::parse_quote!(expr(#text))
+            }
+        }
+    }).collect();
+
+    Ok(syn::Meta::List(syn::MetaList {
+        path: attr.path.clone(),
+        paren_token: syn::token::Paren::default(),
+        nested,
+    }))
+}
+
+
+/// Display attribute `error_kind("...")` from the variant doc comments
+fn parse_doc_meta(variant: &syn::Variant) -> syn::Result<syn::Meta> {
+    let mut text = String::new();
+    let mut span = Span::call_site();
+
+    for attr in variant.attrs.iter().filter(|v| v.path.is_ident("doc")) {
+        if let Ok(syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(v), .. })) = attr.parse_meta() {
+            let line = v.value();
+            let line = line.trim();
+            if line.is_empty() {
+                continue
+            }
+            if text.is_empty() {
+                span = v.span();
+            } else {
+                text.push(' ');
+            }
+            text.push_str(line);
+        }
+    }
+
+    if text.is_empty() {
+        return Err(syn::Error::new_spanned(&variant.ident, "error_doc required doc comment"))
+    }
+
+    let text = syn::LitStr::new(&text, span);
+    Ok(syn::parse_quote!(error_kind(#text)))
+}
+
+
+/// Leftmost receiver of the expression: `0` in `0.kind()`
+fn expr_receiver(expr: &mut syn::Expr) -> &mut syn::Expr {
+    match expr {
+        syn::Expr::MethodCall(v) => expr_receiver(&mut v.receiver),
+        syn::Expr::Field(v) => expr_receiver(&mut v.base),
+        syn::Expr::Index(v) => expr_receiver(&mut v.expr),
+        syn::Expr::Reference(v) => expr_receiver(&mut v.expr),
+        syn::Expr::Unary(v) => expr_receiver(&mut v.expr),
+        syn::Expr::Paren(v) => expr_receiver(&mut v.expr),
+        expr => expr,
+    }
+}
+
+
+/// Source type of the variant defined with `#[error_from(primary)]`
+fn primary_type(variant: &syn::Variant) -> Option<String> {
+    let attr = variant.attrs.iter().find(|v| v.path.is_ident("error_from"))?;
+    let meta_list = match parse_display_meta(attr) {
+        Ok(syn::Meta::List(v)) => v,
+        _ => return None,
+    };
+
+    let (options, _) = parse_from_options(&meta_list).ok()?;
+    if ! options.primary || variant.fields.is_empty() {
+        return None
+    }
+
+    match &options.from {
+        Some(ty) => Some(type_to_string(ty)),
+        None => source_field(&variant.fields).ok().map(|(_, v)| type_to_string(&v.ty)),
+    }
+}
+
+
+/// Expands `#[error_format]` template: `{variant}` and `{fields}` replaced
+/// with the variant name and fields format, `{prefix}` replaced with the
+/// named argument `error_rules_prefix`
+fn expand_format(template: &str, variant: &str, fields: &str) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                result.push_str("{{");
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err("unclosed placeholder".to_owned()),
+                    }
+                }
+                match name.as_str() {
+                    "variant" => result.push_str(variant),
+                    "fields" => result.push_str(fields),
+                    "prefix" => result.push_str("{error_rules_prefix}"),
+                    _ => return Err(format!("unknown placeholder {{{}}}, \
+                        expected {{prefix}}, {{variant}} or {{fields}}", name)),
+                }
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                result.push_str("}}");
+            }
+            '}' => return Err("unmatched `}` in format string".to_owned()),
+            c => result.push(c),
+        }
+    }
+    Ok(result)
+}
+
+
+/// Return type of the accessor forwarded with `#[error_delegate]`.
+/// Types generated for the inner error, like `{Inner}Severity`,
+/// are resolved relative to the inner type path
+fn delegate_type(method: &Ident, inner: &syn::Type) -> Option<TokenStream> {
+    let suffix = match method.to_string().as_str() {
+        "code" => return Some(quote! { u32 }),
+        "status" => return Some(quote! { u16 }),
+        "fingerprint" => return Some(quote! { u64 }),
+        "variant_name" => return Some(quote! { &'static str }),
+        "group" | "help" | "url" => return Some(quote! { Option<&'static str> }),
+        "is_timeout" | "is_cancelled" | "is_transient" => return Some(quote! { bool }),
+        "exit_code" => return Some(quote! { ::std::process::ExitCode }),
+        "severity" => "Severity",
+        "kind" => "Kind",
+        "category" => "Category",
+        _ => return None,
+    };
+
+    let mut path = match inner {
+        syn::Type::Path(v) if v.qself.is_none() => v.path.clone(),
+        _ => return None,
+    };
+    let segment = path.segments.last_mut().unwrap();
+    if ! segment.arguments.is_empty() {
+        return None
+    }
+    segment.ident = Ident::new(&format!("{}{}", segment.ident, suffix), segment.ident.span());
+
+    if suffix == "Category" {
+        Some(quote! { Option<#path> })
+    } else {
+        Some(quote! { #path })
+    }
+}
+
+
+/// Pattern to bind all variant fields: unnamed fields as `i0`, `i1`, ...
+fn impl_fields_pattern(fields: &syn::Fields) -> TokenStream {
+    match fields {
+        syn::Fields::Unit => TokenStream::new(),
+        syn::Fields::Unnamed(fields) => {
+            let ident_list = (0 .. fields.unnamed.len())
+                .map(|i| Ident::new(&format!("i{}", i), Span::call_site()));
+            quote! { ( #( #ident_list ),* ) }
+        }
+        syn::Fields::Named(fields) => {
+            let ident_list = fields.named.iter()
+                .map(|v| v.ident.as_ref().unwrap());
+            quote! { { #( #ident_list ),* } }
+        }
+    }
+}
+
+
+struct ErrorRules {
+    enum_id: Ident,
+    vis: syn::Visibility,
+    generics: syn::Generics,
+    prefix: String,
+    prefix_module: bool,
+    remote: Option<syn::Path>,
+    format: Option<syn::LitStr>,
+    result: Option<String>,
+    is_struct: bool,
+    audit: bool,
+    truncate: Option<usize>,
+    eq_kind: bool,
+    snapshot: bool,
+    lazy: bool,
+    hex: bool,
+    truncate_field: bool,
+    io_class: bool,
+    map: bool,
+    delegate: bool,
+    delegate_list: Vec<Ident>,
+    constructors: bool,
+    accessors: bool,
+    single_variant: bool,
+    kind_enum: bool,
+    termination: bool,
+    eq: bool,
+    eq_total: bool,
+    no_std: bool,
+    ffi: bool,
+    trace: Option<Ident>,
+    variant_name: bool,
+    fingerprint: bool,
+    chain: bool,
+    serialize: bool,
+    diagnostic: bool,
+    log: bool,
+    report: bool,
+    wasm: bool,
+    axum: bool,
+    tonic: bool,
+    grpc_default: Option<Ident>,
+    pyo3: bool,
+    py_default: Option<TokenStream>,
+    defmt: bool,
+    deserialize: bool,
+    serialize_has_fields: bool,
+    wire_list: TokenStream,
+    wire_since_list: TokenStream,
+    from_list: TokenStream,
+    source_list: TokenStream,
+    display_list: TokenStream,
+    variant_list: TokenStream,
+    cfg: TokenStream,
+    deserialize_list: TokenStream,
+    deserialize_from_list: TokenStream,
+    serialize_fields_list: TokenStream,
+    serialize_fields_match_list: TokenStream,
+    remote_list: TokenStream,
+    audit_list: TokenStream,
+    group_list: TokenStream,
+    source_type_list: Vec<syn::Type>,
+    source_adapter: bool,
+    path_adapter: bool,
+    primary_list: Vec<String>,
+    truncate_list: TokenStream,
+    eq_kind_list: TokenStream,
+    eq_type_list: Vec<syn::Type>,
+    timeout_list: TokenStream,
+    cancelled_list: TokenStream,
+    deadline_list: TokenStream,
+    or_kind: bool,
+    sink: bool,
+    problem_json: bool,
+    crash_report: bool,
+    syslog: bool,
+    examples: bool,
+    example_list: TokenStream,
+    or_kind_list: TokenStream,
+    map_list: TokenStream,
+    transparent_list: TokenStream,
+    format_prefix_list: TokenStream,
+    builder_list: TokenStream,
+    delegate_impl: TokenStream,
+    constructor_list: TokenStream,
+    accessor_list: TokenStream,
+    kind_list: TokenStream,
+    kind_match_list: TokenStream,
+    code_list: TokenStream,
+    code_table: Vec<(u32, String, TokenStream)>,
+    code_missing_list: Vec<Ident>,
+    help_list: TokenStream,
+    url_list: TokenStream,
+    backtrace_list: TokenStream,
+    location_list: TokenStream,
+    severity_list: TokenStream,
+    exit_code_list: TokenStream,
+    transient_list: TokenStream,
+    io_kind_list: TokenStream,
+    into_io_list: TokenStream,
+    status_list: TokenStream,
+    grpc_list: TokenStream,
+    py_list: TokenStream,
+    py_category_list: TokenStream,
+    defmt_list: TokenStream,
+    category_list: TokenStream,
+    category_names: Vec<Ident>,
+    error_list: Vec<syn::Error>,
+}
+
+
+impl ErrorRules {
+    fn new(input: &syn::DeriveInput) -> ErrorRules {
+        ErrorRules {
+            enum_id: input.ident.clone(),
+            vis: input.vis.clone(),
+            generics: input.generics.clone(),
+            prefix: String::default(),
+            prefix_module: false,
+            remote: None,
+            format: None,
+            result: None,
+            is_struct: false,
+            audit: false,
+            truncate: None,
+            eq_kind: false,
+            snapshot: false,
+            lazy: false,
+            hex: false,
+            truncate_field: false,
+            io_class: false,
+            map: false,
+            delegate: false,
+            delegate_list: Vec::default(),
+            constructors: false,
+            accessors: false,
+            single_variant: false,
+            kind_enum: false,
+            termination: false,
+            eq: false,
+            eq_total: false,
+            no_std: false,
+            ffi: false,
+            trace: None,
+            variant_name: false,
+            fingerprint: false,
+            chain: false,
+            serialize: false,
+            diagnostic: false,
+            log: false,
+            report: false,
+            wasm: false,
+            axum: false,
+            tonic: false,
+            grpc_default: None,
+            pyo3: false,
+            py_default: None,
+            defmt: false,
+            deserialize: false,
+            serialize_has_fields: false,
+            wire_list: TokenStream::default(),
+            wire_since_list: TokenStream::default(),
+            from_list: TokenStream::default(),
+            source_list: TokenStream::default(),
+            display_list: TokenStream::default(),
+            variant_list: TokenStream::default(),
+            cfg: TokenStream::default(),
+            deserialize_list: TokenStream::default(),
+            deserialize_from_list: TokenStream::default(),
+            serialize_fields_list: TokenStream::default(),
+            serialize_fields_match_list: TokenStream::default(),
+            remote_list: TokenStream::default(),
+            audit_list: TokenStream::default(),
+            group_list: TokenStream::default(),
+            source_type_list: Vec::default(),
+            source_adapter: false,
+            path_adapter: false,
+            primary_list: Vec::default(),
+            truncate_list: TokenStream::default(),
+            eq_kind_list: TokenStream::default(),
+            eq_type_list: Vec::default(),
+            timeout_list: TokenStream::default(),
+            cancelled_list: TokenStream::default(),
+            deadline_list: TokenStream::default(),
+            or_kind: false,
+            sink: false,
+            problem_json: false,
+            crash_report: false,
+            syslog: false,
+            examples: false,
+            example_list: TokenStream::default(),
+            or_kind_list: TokenStream::default(),
+            map_list: TokenStream::default(),
+            transparent_list: TokenStream::default(),
+            format_prefix_list: TokenStream::default(),
+            builder_list: TokenStream::default(),
+            delegate_impl: TokenStream::default(),
+            constructor_list: TokenStream::default(),
+            accessor_list: TokenStream::default(),
+            kind_list: TokenStream::default(),
+            kind_match_list: TokenStream::default(),
+            code_list: TokenStream::default(),
+            code_table: Vec::default(),
+            code_missing_list: Vec::default(),
+            help_list: TokenStream::default(),
+            url_list: TokenStream::default(),
+            backtrace_list: TokenStream::default(),
+            location_list: TokenStream::default(),
+            severity_list: TokenStream::default(),
+            exit_code_list: TokenStream::default(),
+            transient_list: TokenStream::default(),
+            io_kind_list: TokenStream::default(),
+            into_io_list: TokenStream::default(),
+            status_list: TokenStream::default(),
+            grpc_list: TokenStream::default(),
+            py_list: TokenStream::default(),
+            py_category_list: TokenStream::default(),
+            defmt_list: TokenStream::default(),
+            category_list: TokenStream::default(),
+            category_names: Vec::default(),
+            error_list: Vec::default(),
+        }
+    }
+
+    /// Generics for the generated impl blocks:
+    /// `impl<...>`, enum type with parameters, and where clause
+    fn split_generics(&self) -> (TokenStream, TokenStream, TokenStream) {
+        let enum_id = &self.enum_id;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        (
+            quote! { #impl_generics },
+            quote! { #enum_id #ty_generics },
+            quote! { #where_clause },
+        )
+    }
+
+    /// Where clause with additional predicates
+    fn where_with(&self, predicate_list: TokenStream) -> TokenStream {
+        let where_list = match &self.generics.where_clause {
+            Some(v) => {
+                let v = v.predicates.iter();
+                quote! { #( #v, )* }
+            }
+            None => TokenStream::new(),
+        };
+
+        quote! { where #where_list #predicate_list }
+    }
+
+    /// Expression with name of the variant of the `value`
+    fn variant_name_expr(&self, value: TokenStream) -> TokenStream {
+        let variant_list = &self.variant_list;
+        quote! {
+            match #value {
+                #variant_list
+            }
+        }
+    }
+
+    /// Reference to the source error from the field reference.
+    /// Box and Arc are dereferenced. Other types resolved with autoref:
+    /// types implemented `Error` used as is, types with `Deref` to the
+    /// `dyn Error`, like `anyhow::Error` or `eyre::Report`, dereferenced
+    fn source_expr(&mut self, ty: &syn::Type, source: TokenStream) -> TokenStream {
+        if is_pointer(ty) {
+            quote! { &**#source }
+        } else {
+            self.source_adapter = true;
+            quote! { (&&__Source(#source)).__source() }
+        }
+    }
+
+    /// Display of the `Path` or `PathBuf` field, other types named `Path` displayed as is
+    fn path_expr(&mut self, ty: &syn::Type, attr_id: &Ident) -> TokenStream {
+        self.path_adapter = true;
+        let deref = (0 ..= ref_depth(ty)).map(|_| quote! { * });
+        quote! { (&&__PathDisplay(&#( #deref )* #attr_id)).__display() }
+    }
+
+    /// Path to the `Error` trait: `core` for `#[error_no_std]`
+    fn error_trait(&self) -> TokenStream {
+        if self.no_std {
+            quote! { ::core::error::Error }
+        } else {
+            quote! { ::std::error::Error }
+        }
+    }
+
+    /// Pushes error of the attribute parsing into the error list
+    fn check<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        match result {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.error_list.push(e);
+                None
+            }
+        }
+    }
+
+    /// Pushes error for the attribute with unexpected format
+    fn meta_mismatch<T: ToTokens>(&mut self, tokens: T) {
+        self.error_list.push(syn::Error::new_spanned(tokens, "meta format mismatch"));
+    }
+
+    /// Pushes error if attribute is used without required `feature`
+    fn check_feature(&mut self, attr: &syn::Attribute, enabled: bool, feature: &str) -> bool {
+        if ! enabled {
+            let message = format!("{} required {} feature", attr.path.segments[0].ident, feature);
+            self.error_list.push(syn::Error::new_spanned(attr, message));
+        }
+        enabled
+    }
+
+    /// Reports attribute which required `std` for `#[error_no_std]`
+    fn check_no_std(&mut self, attr: &syn::Attribute) {
+        let name = match attr.path.get_ident() {
+            Some(v) => v.to_string(),
+            None => return,
+        };
+        match name.as_str() {
+            "error_snapshot" |
+            "error_fingerprint" |
+            "error_truncate" |
+            "error_termination" |
+            "error_exit_code" |
+            "error_ffi" |
+            "error_io_class" |
+            "error_io_kind" |
+            "error_into_io" |
+            "error_serialize" |
+            "error_deserialize" |
+            "error_diagnostic" |
+            "error_log" |
+            "error_or_kind" |
+            "error_sink" |
+            "error_examples" |
+            "error_report" |
+            "error_wasm" |
+            "error_axum" |
+            "error_problem_json" |
+            "error_crash_report" |
+            "error_syslog" |
+            "error_grpc" |
+            "error_py" => {
+                let message = format!("{} required std and could not be used with error_no_std", name);
+                self.error_list.push(syn::Error::new_spanned(attr, message));
+            }
+            _ => {},
+        }
+    }
+
+    fn impl_display_attr(&mut self,
+        attr: &syn::NestedMeta,
+        fields: &syn::Fields,
+        used_list: &mut Vec<Ident>) -> TokenStream
+    {
+        if let syn::NestedMeta::Meta(syn::Meta::List(v)) = attr {
+            if v.path.is_ident("expr") {
+                return self.check(impl_display_expr(v, fields, used_list))
+                    .unwrap_or_else(|| quote! { () })
+            }
+        }
+
+        let (adapter, attr) = match attr {
+            syn::NestedMeta::Meta(syn::Meta::List(v)) if v.path.is_ident("hex") || v.path.is_ident("utf8") => {
+                match v.nested.first() {
+                    Some(attr) => (Some(v), attr),
+                    None => {
+                        let message = "attributes should be number";
+                        self.error_list.push(syn::Error::new_spanned(v, message));
+                        return quote! { () }
+                    }
+                }
+            }
+            attr => (None, attr),
+        };
+
+        if let syn::NestedMeta::Lit(syn::Lit::Int(v)) = attr {
+            let index = v.base10_parse::<usize>().unwrap_or(usize::MAX);
+            if index >= fields.len() {
+                let message = format!("field index {} out of range, variant has {} fields", index, fields.len());
+                self.error_list.push(syn::Error::new(v.span(), message));
+            }
+        }
+
+        let (attr_id, field) = match parse_field_ref(attr, fields) {
+            Ok(v) => v,
+            Err(e) => {
+                self.error_list.push(e);
+                return quote! { () }
+            }
+        };
+        if field.and_then(|v| v.ident.as_ref()).is_some() && ! used_list.contains(&attr_id) {
+            used_list.push(attr_id.clone());
+        }
+
+        if let Some(adapter) = adapter {
+            if adapter.path.is_ident("utf8") {
+                return quote! { String::from_utf8_lossy(AsRef::<[u8]>::as_ref(#attr_id)) }
+            }
+
+            let limit = match adapter.nested.iter().nth(1) {
+                Some(syn::NestedMeta::Lit(syn::Lit::Int(v))) => match self.check(v.base10_parse::<usize>()) {
+                    Some(v) => v,
+                    None => return quote! { () },
+                },
+                None => 32,
+                Some(v) => {
+                    let message = "attributes should be number";
+                    self.error_list.push(syn::Error::new_spanned(v, message));
+                    return quote! { () }
+                }
+            };
+            self.hex = true;
+            return quote! { DisplayHex(AsRef::<[u8]>::as_ref(#attr_id), #limit) }
+        }
+
+        let expr = match field {
+            Some(field) if is_path(&field.ty) => self.path_expr(&field.ty, &attr_id),
+            Some(field) if is_lazy_display(&field.ty) => {
+                self.lazy = true;
+                quote! { LazyDisplay(&**#attr_id) }
+            }
+            _ => quote! { #attr_id },
+        };
+
+        match field.and_then(field_truncate) {
+            Some(limit) => {
+                self.truncate_field = true;
+                quote! { DisplayTruncate(&#expr, #limit) }
+            }
+            None => expr,
+        }
+    }
+
+    /// Returns arguments for the `write!` macro and list of the used named fields
+    fn impl_display_item(&mut self,
+        meta_list: &syn::MetaList,
+        fields: &syn::Fields) -> (TokenStream, Vec<Ident>)
+    {
+        let mut attr_list = TokenStream::new();
+        let mut used_list = Vec::new();
+
+        let fmt_lit = match &meta_list.nested[0] {
+            syn::NestedMeta::Lit(syn::Lit::Str(v)) => v,
+            v => {
+                let message = "first attribute shoud be literal";
+                self.error_list.push(syn::Error::new_spanned(v, message));
+                return (quote! { "" }, used_list)
+            }
+        };
+        let mut fmt = fmt_lit.value();
+
+        if let Err(e) = check_fmt_args(meta_list, fields) {
+            self.error_list.push(e);
+        }
+
+        if meta_list.nested.len() == 1 {
+            fmt = fmt_inline_positions(&fmt);
+        }
+
+        let mut path_list = Vec::new();
+
+        for arg in fmt_args(&fmt) {
+            let field = fields.iter().enumerate().find(|(i, v)| match &v.ident {
+                Some(id) => id == arg.as_str(),
+                None => arg == format!("i{}", i),
+            });
+            if let Some((i, field)) = field {
+                let attr_id = match &field.ident {
+                    Some(v) => v.clone(),
+                    None => Ident::new(&format!("i{}", i), Span::call_site()),
+                };
+                if field.ident.is_some() && ! used_list.contains(&attr_id) {
+                    used_list.push(attr_id.clone());
+                }
+                if (is_path(&field.ty) || field_truncate(field).is_some()) && ! path_list.iter().any(|(v, _)| v == &attr_id) {
+                    path_list.push((attr_id, field));
+                }
+            }
+        }
+
+        let fmt = syn::LitStr::new(&fmt, fmt_lit.span());
+        attr_list.extend(quote! { #fmt });
+
+        for attr in meta_list.nested.iter().skip(1) {
+            let attr = self.impl_display_attr(attr, fields, &mut used_list);
+            attr_list.extend(quote! { , #attr });
+        }
+
+        for (attr_id, field) in path_list {
+            let mut expr = quote! { #attr_id };
+            if is_path(&field.ty) {
+                expr = self.path_expr(&field.ty, &attr_id);
+            }
+            if let Some(limit) = field_truncate(field) {
+                self.truncate_field = true;
+                expr = quote! { DisplayTruncate(&#expr, #limit) };
+            }
+            attr_list.extend(quote! { , #attr_id = #expr });
+        }
+
+        (attr_list, used_list)
+    }
+
+    fn impl_error_from_fields(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        options: &FromOptions) -> Ident
+    {
+        let cfg = self.cfg.clone();
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+
+        if let syn::Fields::Unit = &variant.fields {
+            panic!("field format mismatch")
+        }
+
+        let (source_idx, field) = source_field(&variant.fields).unwrap();
+        let member = match &field.ident {
+            Some(v) => syn::Member::Named(v.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(source_idx)),
+        };
+
+        // other fields without value skip `From` unless `default` option defined
+        let mut has_other = false;
+        let init_list: Vec<TokenStream> = variant.fields.iter().enumerate().map(|(i, v)| {
+            let value = if i == source_idx {
+                quote! { e }
+            } else if let Some(capture) = field_capture(v) {
+                capture
+            } else {
+                has_other = true;
+                quote! { Default::default() }
+            };
+            match &v.ident {
+                Some(id) => quote! { #id: #value },
+                None => value,
+            }
+        }).collect();
+        let init = match &variant.fields {
+            syn::Fields::Named(_) => quote! { { #( #init_list ),* } },
+            _ => quote! { ( #( #init_list ),* ) },
+        };
+
+        let ty = &field.ty;
+        let source = self.source_expr(ty, quote! { source });
+        if options.transparent {
+            let error_trait = self.error_trait();
+            self.source_list.extend(quote! {
+                #cfg
+                #item_id { #member: source, .. } => #error_trait::source(#source),
+            });
+        } else {
+            self.source_list.extend(quote! {
+                #cfg
+                #item_id { #member: source, .. } => Some(#source),
+            });
+        }
+        self.source_type_list.push(ty.clone());
+
+        let with = &options.with;
+        let from = options.from.as_ref().unwrap_or(ty);
+        let (track_caller, body) = self.impl_trace(variant, quote! { #item_id #init });
+        let no_from = options.no_from || (has_other && ! options.default) || (
+            ! options.primary &&
+            self.primary_list.contains(&type_to_string(from))
+        );
+
+        match &with {
+            _ if no_from => {}
+            Some(with) => {
+                self.from_list.extend(quote! {
+                    #cfg
+                    impl #impl_generics From<#from> for #enum_ty #where_clause {
+                        #[inline]
+                        #track_caller
+                        fn from(e: #from) -> Self {
+                            let e: #ty = #with(e);
+                            #body
+                        }
+                    }
+                });
+            }
+            None => {
+                self.from_list.extend(quote! {
+                    #cfg
+                    impl #impl_generics From<#ty> for #enum_ty #where_clause {
+                        #[inline]
+                        #track_caller
+                        fn from(e: #ty) -> Self { #body }
+                    }
+                });
+            }
+        };
+
+        if self.audit && ! no_from {
+            let ty = type_to_string(from);
+            let name = variant.ident.to_string();
+            self.audit_list.extend(quote! {
+                #cfg
+                (#ty, #name),
+            });
+        }
+
+        match &field.ident {
+            Some(v) => v.clone(),
+            None => Ident::new(&format!("i{}", source_idx), Span::call_site()),
+        }
+    }
+
+    fn impl_error_from_path(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        options: &FromOptions)
+    {
+        let cfg = self.cfg.clone();
+        let source_id = self.impl_error_from_fields(item_id, variant, options);
+
+        let pattern = match &variant.fields {
+            syn::Fields::Named(_) => quote! { { #source_id, .. } },
+            fields => impl_fields_pattern(fields),
+        };
+        self.display_list.extend(quote! {
+            #cfg
+            #item_id #pattern => write!(f, "{}", #source_id),
+        });
+    }
+
+    fn impl_error_from_list(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta_list: &syn::MetaList)
+    {
+        let cfg = self.cfg.clone();
+        let (options, meta_list) = match self.check(parse_from_options(meta_list)) {
+            Some(v) => v,
+            None => return,
+        };
+        if meta_list.nested.is_empty() {
+            self.impl_error_from_path(item_id, variant, &options);
+            return
+        }
+
+        let source_id = self.impl_error_from_fields(item_id, variant, &options);
+
+        let (mut w, mut used_list) = self.impl_display_item(&meta_list, &variant.fields);
+        if let syn::NestedMeta::Lit(syn::Lit::Str(v)) = &meta_list.nested[0] {
+            if fmt_has_named(&v.value(), "source") {
+                w.extend(quote! { , source = #source_id });
+                if ! used_list.contains(&source_id) {
+                    used_list.push(source_id);
+                }
+            }
+        }
+
+        let pattern = match &variant.fields {
+            syn::Fields::Named(_) => quote! { { #( #used_list, )* .. } },
+            fields => impl_fields_pattern(fields),
+        };
+        self.display_list.extend(quote! {
+            #cfg
+            #item_id #pattern => write!(f, #w),
+        });
+    }
+
+    fn impl_error_from(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta: &syn::Meta)
+    {
+        match meta {
+            syn::Meta::Path(_) => self.impl_error_from_path(item_id, variant, &FromOptions::default()),
+            syn::Meta::List(v) => self.impl_error_from_list(item_id, variant, v),
+            _ => self.meta_mismatch(meta),
+        }
+    }
+
+    fn impl_error_transparent(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        // `from` and `with` options added by `#[error_clone]`
+        let mut options = match meta {
+            syn::Meta::List(v) => {
+                let (options, display) = match self.check(parse_from_options(v)) {
+                    Some(v) => v,
+                    None => return,
+                };
+                if let Some(v) = display.nested.first() {
+                    let message = "error_transparent accepts only from and with options";
+                    self.error_list.push(syn::Error::new_spanned(v, message));
+                }
+                options
+            }
+            _ => FromOptions::default(),
+        };
+        options.transparent = true;
+        self.impl_error_from_path(item_id, variant, &options);
+
+        if self.delegate {
+            self.impl_error_delegate(item_id, variant);
+        } else {
+            self.transparent_list.extend(quote! {
+                #cfg
+                #item_id { .. } => true,
+            });
+        }
+    }
+
+    fn impl_error_delegate(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let (source_idx, field) = source_field(&variant.fields).unwrap();
+        let member = match &field.ident {
+            Some(v) => syn::Member::Named(v.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(source_idx)),
+        };
+
+        let mut method_list = TokenStream::new();
+        for method in &self.delegate_list {
+            if method == "variant_name" {
+                self.variant_list.extend(quote! {
+                    #cfg
+                    #item_id { #member: inner, .. } => inner.variant_name(),
+                });
+                continue
+            }
+
+            let ty = match delegate_type(method, &field.ty) {
+                Some(v) => v,
+                None => {
+                    let message = "error_delegate could forward code, status, \
+                        variant_name, group, help, url, is_timeout, is_cancelled, \
+                        is_transient, fingerprint, exit_code, and severity, kind \
+                        or category if inner error is a type path";
+                    self.error_list.push(syn::Error::new_spanned(method, message));
+                    continue
+                }
+            };
+            let doc = format!("Returns `{}()` of the inner error", method);
+            method_list.extend(quote! {
+                #[doc = #doc]
+                #[inline]
+                pub fn #method(&self) -> #ty {
+                    let #item_id { #member: inner, .. } = self;
+                    inner.#method()
+                }
+            });
+        }
+
+        if ! method_list.is_empty() {
+            self.delegate_impl.extend(quote! {
+                #cfg
+                impl #impl_generics #enum_ty #where_clause {
+                    #method_list
+                }
+            });
+        }
+    }
+
+    fn impl_error_kind_list(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta_list: &syn::MetaList)
+    {
+        let cfg = self.cfg.clone();
+        if meta_list.nested.is_empty() {
+            return self.meta_mismatch(meta_list)
+        }
+
+        if self.defmt {
+            self.impl_error_defmt(item_id, variant, meta_list);
+        }
+
+        match &variant.fields {
+            syn::Fields::Unit => {
+                let (w, _) = self.impl_display_item(meta_list, &variant.fields);
+                self.display_list.extend(quote! {
+                    #cfg
+                    #item_id => write!(f, #w),
+                });
+            }
+            syn::Fields::Unnamed(fields) => {
+                let mut ident_list = TokenStream::new();
+                for i in 0 .. fields.unnamed.len() {
+                    let field_id = Ident::new(&format!("i{}", i), Span::call_site());
+                    ident_list.extend(quote! { #field_id, });
+                }
+
+                let (w, _) = self.impl_display_item(meta_list, &variant.fields);
+                self.display_list.extend(quote! {
+                    #cfg
+                    #item_id ( #ident_list ) => write!(f, #w),
+                });
+            }
+            syn::Fields::Named(_) => {
+                let (w, used_list) = self.impl_display_item(meta_list, &variant.fields);
+                self.display_list.extend(quote! {
+                    #cfg
+                    #item_id { #( #used_list, )* .. } => write!(f, #w),
+                });
+            }
+        };
+    }
+
+    fn impl_error_defmt(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta_list: &syn::MetaList)
+    {
+        let cfg = self.cfg.clone();
+        let (fmt, arg_list) = match defmt_fmt(meta_list, &variant.fields) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let mut used_list = Vec::new();
+        for arg_id in arg_list.iter() {
+            if ! used_list.contains(arg_id) {
+                used_list.push(arg_id.clone());
+            }
+        }
+        let pattern = match &variant.fields {
+            syn::Fields::Unit => TokenStream::new(),
+            syn::Fields::Unnamed(_) => impl_fields_pattern(&variant.fields),
+            syn::Fields::Named(_) => quote! { { #( #used_list, )* .. } },
+        };
+
+        self.defmt_list.extend(quote! {
+            #cfg
+            #item_id #pattern => defmt::write!(f, #fmt #( , #arg_list )*),
+        });
+    }
+
+    fn impl_error_kind(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta: &syn::Meta)
+    {
+        match meta {
+            syn::Meta::List(v) => self.impl_error_kind_list(item_id, variant, v),
+            _ => self.meta_mismatch(meta),
+        }
+    }
+
+    fn impl_variant(&mut self, variant: &syn::Variant) {
+        let cfg_list = variant.attrs.iter().filter(|v| v.path.is_ident("cfg"));
+        self.cfg = quote! { #( #cfg_list )* };
+        let cfg = self.cfg.clone();
+        let enum_id = &self.enum_id;
+        let item_id = &variant.ident;
+        let item_id = if self.is_struct {
+            quote! { #enum_id }
+        } else {
+            quote! { #enum_id::#item_id }
+        };
+
+        if ! self.delegate_list.iter().any(|v| v == "variant_name") {
+            let name = variant.ident.to_string();
+            self.variant_list.extend(quote! {
+                #cfg
+                #item_id { .. } => #name,
+            });
+        }
+
+        if self.kind_enum {
+            let kind_id = Ident::new(&format!("{}Kind", self.enum_id), Span::call_site());
+            let variant_id = &variant.ident;
+            self.kind_list.extend(quote! { #cfg #variant_id, });
+            self.kind_match_list.extend(quote! {
+                #cfg
+                #item_id { .. } => #kind_id::#variant_id,
+            });
+        }
+
+        if self.examples {
+            self.impl_error_example(&item_id, variant);
+        }
+
+        if let Some(remote) = &self.remote {
+            let remote_id = &variant.ident;
+            let remote = remote.segments.iter().map(|v| &v.ident);
+            let remote_id = quote! { #( #remote )::* :: #remote_id };
+            let fields = impl_fields_pattern(&variant.fields);
+            self.remote_list.extend(quote! {
+                #cfg
+                #remote_id #fields => #item_id #fields,
+            });
+        }
+
+        let mut display = None;
+        let mut has_builder = false;
+        let mut has_code = false;
+        let mut io_kind = None;
+        let mut into_io = None;
+        let mut wire = None;
+        if self.delegate {
+            display = Some(("error_transparent".to_owned(), syn::parse_quote!(error_transparent)));
+        }
+
+        for attr in variant.attrs.iter().filter(|v| v.path.segments.len() == 1) {
+            if self.no_std && ! self.is_struct {
+                self.check_no_std(attr);
+            }
+            let name = attr.path.segments[0].ident.to_string();
+            match name.as_str() {
+                "error_from" | "error_kind" | "error_transparent" if display.is_none() => {
+                    if let Some(meta) = self.check(parse_display_meta(attr)) {
+                        display = Some((name, meta));
+                    }
+                }
+                "error_doc" if display.is_none() => {
+                    if let Some(meta) = self.check(parse_doc_meta(variant)) {
+                        display = Some(("error_kind".to_owned(), meta));
+                    }
+                }
+                "error_group" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_group(&item_id, &meta);
+                }
+                "error_timeout" => {
+                    self.timeout_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => true,
+                    });
+                    if let Some(syn::Meta::List(meta)) = self.check(attr.parse_meta()) {
+                        self.impl_error_deadline(&item_id, variant, &meta);
+                    }
+                }
+                "error_cancelled" => {
+                    self.cancelled_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => true,
+                    });
+                }
+                "error_builder" => {
+                    self.impl_error_builder(&item_id, variant);
+                    has_builder = true;
+                }
+                "error_code" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_code(&item_id, variant, &meta);
+                    has_code = true;
+                }
+                "error_help" => if let Some(help) = self.check(attr.parse_meta().and_then(|v| parse_text(&v))) {
+                    self.help_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => Some(#help),
+                    });
+                }
+                "error_url" => if let Some(url) = self.check(attr.parse_meta().and_then(|v| parse_text(&v))) {
+                    self.url_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => Some(#url),
+                    });
+                }
+                "error_category" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_category(&item_id, &meta);
+                }
+                "error_transient" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_transient(&item_id, variant, &meta);
+                }
+                "error_io_kind" => {
+                    io_kind = self.check(attr.parse_meta().and_then(|v| parse_kind(&v)));
+                }
+                "error_into_io" => into_io = Some(attr),
+                "error_wire" => wire = Some(attr),
+                "error_grpc" => {
+                    if ! self.tonic {
+                        let message = "error_grpc(Code) required error_grpc before enum declaration";
+                        self.error_list.push(syn::Error::new_spanned(attr, message));
+                    }
+                    let code = match self.check(attr.parse_meta().and_then(|v| parse_kind(&v))) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    self.grpc_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => tonic::Code::#code,
+                    });
+                }
+                "error_py" => {
+                    if ! self.pyo3 {
+                        let message = "error_py(Exception) required error_py before enum declaration";
+                        self.error_list.push(syn::Error::new_spanned(attr, message));
+                    }
+                    let exception = match self.check(attr.parse_meta()) {
+                        Some(syn::Meta::List(v)) if v.nested.len() == 1 => match &v.nested[0] {
+                            syn::NestedMeta::Meta(syn::Meta::Path(v)) => py_exception(v),
+                            _ => {
+                                self.meta_mismatch(attr);
+                                continue
+                            }
+                        },
+                        Some(_) => {
+                            self.meta_mismatch(attr);
+                            continue
+                        }
+                        None => continue,
+                    };
+                    self.py_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => #exception::new_err(message),
+                    });
+                }
+                "error_status" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_status(&item_id, &meta);
+                }
+                "error_exit_code" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_exit_code(&item_id, &meta);
+                }
+                "error_severity" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.impl_error_severity(&item_id, &meta);
+                }
+                "error_truncate" => if let Some(limit) = self.check(attr.parse_meta().and_then(|v| parse_truncate(&v))) {
+                    self.truncate_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => Some(#limit),
+                    });
+                }
+                _ => {},
+            }
+        }
+
+        let field_attrs = variant.fields.iter()
+            .flat_map(|v| &v.attrs)
+            .filter(|v| v.path.is_ident("error_truncate"));
+        for attr in field_attrs {
+            self.check(attr.parse_meta().and_then(|v| parse_truncate(&v)));
+        }
+
+        let is_source = matches!(&display,
+            Some((name, _)) if name == "error_from" || name == "error_transparent");
+        if is_source {
+            if let Err(e) = source_field(&variant.fields) {
+                self.error_list.push(e);
+                return
+            }
+        }
+        if let Some((i, field)) = variant.fields.iter().enumerate().find(|(_, v)| is_optional_source(v)) {
+            if is_source {
+                let message = "optional source could not be used with error_from or error_transparent";
+                self.error_list.push(syn::Error::new_spanned(field, message));
+                return
+            }
+            self.impl_error_optional_source(&item_id, i, field);
+            if self.or_kind {
+                self.impl_error_or_kind(&item_id, i, field);
+            }
+        }
+        if self.eq_kind {
+            self.impl_eq_kind(&item_id, variant, is_source);
+        }
+        if self.map {
+            self.impl_error_map(&item_id, variant, is_source);
+        }
+        let rename = wire.and_then(|attr| self.impl_error_wire(&item_id, attr));
+        if self.deserialize {
+            self.impl_error_deserialize(&item_id, variant, is_source, rename.as_ref());
+        }
+        if let Some(kind) = io_kind {
+            self.io_kind_list.extend(quote! {
+                #cfg
+                #item_id { .. } => ::std::io::ErrorKind::#kind,
+            });
+        } else if is_source {
+            let (source_idx, field) = source_field(&variant.fields).unwrap();
+            let member = match &field.ident {
+                Some(v) => syn::Member::Named(v.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(source_idx)),
+            };
+            let source = self.source_expr(&field.ty, quote! { source });
+            self.io_kind_list.extend(quote! {
+                #cfg
+                #item_id { #member: source, .. } => {
+                    let source: &(dyn ::std::error::Error + 'static) = #source;
+                    source.downcast_ref::<::std::io::Error>()
+                        .map_or(::std::io::ErrorKind::Other, |e| e.kind())
+                }
+            });
+        }
+        if let Some(attr) = into_io {
+            if is_source {
+                let (source_idx, field) = source_field(&variant.fields).unwrap();
+                let member = match &field.ident {
+                    Some(v) => syn::Member::Named(v.clone()),
+                    None => syn::Member::Unnamed(syn::Index::from(source_idx)),
+                };
+                self.into_io_list.extend(quote! {
+                    #cfg
+                    #item_id { #member: source, .. } => return source,
+                });
+            } else {
+                let message = "error_into_io required error_from or error_transparent with std::io::Error";
+                self.error_list.push(syn::Error::new_spanned(attr, message));
+            }
+        }
+        if ! has_code {
+            self.code_missing_list.push(variant.ident.clone());
+        }
+        if let Some((i, field)) = variant.fields.iter().enumerate().find(|(_, v)| is_location(v)) {
+            let member = match &field.ident {
+                Some(v) => syn::Member::Named(v.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(i)),
+            };
+            self.location_list.extend(quote! {
+                #cfg
+                #item_id { #member: location, .. } => Some(*location),
+            });
+        }
+        if let Some((i, field)) = variant.fields.iter().enumerate().find(|(_, v)| is_backtrace(v)) {
+            if self.no_std {
+                let message = "backtrace required std and could not be used with error_no_std";
+                self.error_list.push(syn::Error::new_spanned(field, message));
+            }
+            let member = match &field.ident {
+                Some(v) => syn::Member::Named(v.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(i)),
+            };
+            self.backtrace_list.extend(quote! {
+                #cfg
+                #item_id { #member: backtrace, .. } => Some(backtrace),
+            });
+        }
+        if self.constructors && ! has_builder {
+            self.impl_error_constructor(&item_id, variant);
+        }
+        if self.accessors && ! self.is_struct {
+            self.impl_error_accessors(&item_id, variant);
+        }
+        if self.accessors && is_source {
+            self.impl_error_into(&item_id, variant);
+        }
+
+        match display {
+            Some((name, meta)) if name == "error_from" => {
+                self.impl_error_from(&item_id, variant, &meta);
+            }
+            Some((name, meta)) if name == "error_transparent" => {
+                self.impl_error_transparent(&item_id, variant, &meta);
+            }
+            Some((_, meta)) => {
+                self.impl_error_kind(&item_id, variant, &meta);
+            }
+            None if self.format.is_some() => {
+                self.impl_error_format(&item_id, variant);
+            }
+            None => {},
+        }
+    }
+
+    fn impl_eq_kind(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        is_source: bool)
+    {
+        let cfg = self.cfg.clone();
+        if is_source || variant.fields.is_empty() {
+            self.eq_kind_list.extend(quote! {
+                #cfg
+                (#item_id { .. }, #item_id { .. }) => true,
+            });
+            return
+        }
+
+        let mut l_list = TokenStream::new();
+        let mut r_list = TokenStream::new();
+        let mut cmp_list = TokenStream::new();
+
+        for (i, field) in variant.fields.iter().enumerate() {
+            if field_capture(field).is_some() {
+                continue
+            }
+            let l = Ident::new(&format!("l{}", i), Span::call_site());
+            let r = Ident::new(&format!("r{}", i), Span::call_site());
+            let member = match &field.ident {
+                Some(v) => quote! { #v },
+                None => {
+                    let i = syn::Index::from(i);
+                    quote! { #i }
+                }
+            };
+            l_list.extend(quote! { #member: #l, });
+            r_list.extend(quote! { #member: #r, });
+            cmp_list.extend(quote! { && #l == #r });
+            self.eq_type_list.push(field.ty.clone());
+        }
+
+        self.eq_kind_list.extend(quote! {
+            #cfg
+            (#item_id { #l_list }, #item_id { #r_list }) => true #cmp_list,
+        });
+    }
+
+    /// Serialized variant tag and wire format version from the
+    /// `#[error_wire(rename = "...", since = "...")]`. Returns renamed tag
+    fn impl_error_wire(&mut self,
+        item_id: &TokenStream,
+        attr: &syn::Attribute) -> Option<syn::LitStr>
+    {
+        let cfg = self.cfg.clone();
+        if ! self.serialize {
+            let message = "error_wire required error_serialize or error_deserialize";
+            self.error_list.push(syn::Error::new_spanned(attr, message));
+            return None
+        }
+
+        let list = match self.check(attr.parse_meta())? {
+            syn::Meta::List(v) if ! v.nested.is_empty() => v,
+            meta => {
+                self.meta_mismatch(meta);
+                return None
+            }
+        };
+
+        let mut rename = None;
+        for item in &list.nested {
+            match item {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(v),
+                    ..
+                })) if path.is_ident("rename") => {
+                    self.wire_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => #v,
+                    });
+                    rename = Some(v.clone());
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(v),
+                    ..
+                })) if path.is_ident("since") => {
+                    self.wire_since_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => Some(#v),
+                    });
+                }
+                _ => {
+                    let message = "error_wire accepts only rename and since options";
+                    self.error_list.push(syn::Error::new_spanned(item, message));
+                }
+            }
+        }
+
+        rename
+    }
+
+    fn impl_error_deserialize(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        is_source: bool,
+        rename: Option<&syn::LitStr>)
+    {
+        let cfg = self.cfg.clone();
+        if is_source || variant.fields.iter().any(is_optional_source) {
+            let message = "error_deserialize required variants without source errors";
+            self.error_list.push(syn::Error::new(variant.ident.span(), message));
+            return
+        }
+
+        let variant_id = &variant.ident;
+        let bind_list: Vec<Ident> = variant.fields.iter().enumerate()
+            .map(|(i, v)| match &v.ident {
+                Some(v) => v.clone(),
+                None => Ident::new(&format!("i{}", i), Span::call_site()),
+            })
+            .collect();
+        let ty_list: Vec<&syn::Type> = variant.fields.iter().map(|v| &v.ty).collect();
+
+        let (repr, fields_repr, pattern) = match &variant.fields {
+            syn::Fields::Unit => (
+                quote! { #variant_id },
+                quote! { #variant_id },
+                TokenStream::new(),
+            ),
+            syn::Fields::Unnamed(_) => (
+                quote! { #variant_id ( #( #ty_list ),* ) },
+                quote! { #variant_id ( #( &'a #ty_list ),* ) },
+                quote! { ( #( #bind_list ),* ) },
+            ),
+            syn::Fields::Named(_) => (
+                quote! { #variant_id { #( #bind_list: #ty_list ),* } },
+                quote! { #variant_id { #( #bind_list: &'a #ty_list ),* } },
+                quote! { { #( #bind_list ),* } },
+            ),
+        };
+
+        // previous tag is kept as alias for the payloads of the old versions
+        let wire = rename.map(|v| {
+            let name = variant_id.to_string();
+            quote! { #[serde(rename = #v, alias = #name)] }
+        });
+        self.deserialize_list.extend(quote! {
+            #cfg
+            #wire
+            #repr,
+        });
+        self.deserialize_from_list.extend(quote! {
+            #cfg
+            __Repr::#variant_id #pattern => #item_id #pattern,
+        });
+        self.serialize_fields_list.extend(quote! {
+            #cfg
+            #fields_repr,
+        });
+        self.serialize_fields_match_list.extend(quote! {
+            #cfg
+            #item_id #pattern => __Fields::#variant_id #pattern,
+        });
+        if ! variant.fields.is_empty() {
+            self.serialize_has_fields = true;
+        }
+    }
+
+    fn impl_error_map(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        is_source: bool)
+    {
+        let cfg = self.cfg.clone();
+        let source_idx = if is_source && ! variant.fields.is_empty() {
+            Some(source_field(&variant.fields).unwrap().0)
+        } else {
+            None
+        };
+
+        let mut bind_list = TokenStream::new();
+        let mut arg_list = Vec::new();
+        let mut ty_list = Vec::new();
+
+        for (i, field) in variant.fields.iter().enumerate() {
+            if source_idx == Some(i) {
+                continue
+            }
+            let v = Ident::new(&format!("v{}", i), Span::call_site());
+            let member = match &field.ident {
+                Some(v) => quote! { #v },
+                None => {
+                    let i = syn::Index::from(i);
+                    quote! { #i }
+                }
+            };
+            bind_list.extend(quote! { #member: #v, });
+            arg_list.push(v);
+            ty_list.push(&field.ty);
+        }
+
+        if ty_list.is_empty() {
+            return
+        }
+
+        let name = to_snake_case(&variant.ident.to_string());
+        let method = method_ident(&format!("map_{}", name));
+        let doc = format!("Changes fields of the `{}` variant, source error is kept as is", variant.ident);
+        self.map_list.extend(quote! {
+            #cfg
+            #[doc = #doc]
+            #[allow(irrefutable_let_patterns)]
+            pub fn #method<F>(mut self, f: F) -> Self
+            where
+                F: FnOnce( #( &mut #ty_list ),* ),
+            {
+                if let #item_id { #bind_list .. } = &mut self {
+                    f( #( #arg_list ),* );
+                }
+                self
+            }
+        });
+    }
+
+    /// `#[track_caller]` attribute and function body which creates error
+    /// and emits tracing event if `#[error_trace]` defined
+    fn impl_trace(&self, variant: &syn::Variant, init: TokenStream) -> (TokenStream, TokenStream) {
+        let level = match &self.trace {
+            Some(v) => v,
+            None => return (impl_track_caller(&variant.fields), init),
+        };
+
+        let name = variant.ident.to_string();
+        let body = quote! {
+            let error = #init;
+            tracing::event!(tracing::Level::#level,
+                variant = #name,
+                location = %::core::panic::Location::caller(),
+                "{}", error);
+            error
+        };
+        (quote! { #[track_caller] }, body)
+    }
+
+    fn impl_error_constructor(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let mut arg_list = Vec::new();
+        let mut ty_list = Vec::new();
+        let mut init_list = Vec::new();
+        for (i, field) in variant.fields.iter().enumerate() {
+            let id = match &field.ident {
+                Some(v) => v.clone(),
+                None => Ident::new(&format!("i{}", i), Span::call_site()),
+            };
+            let value = match field_capture(field) {
+                Some(capture) => capture,
+                None => {
+                    arg_list.push(id.clone());
+                    ty_list.push(&field.ty);
+                    quote! { #id }
+                }
+            };
+            init_list.push(match &field.ident {
+                Some(_) => quote! { #id: #value },
+                None => value,
+            });
+        }
+
+        let init = match &variant.fields {
+            syn::Fields::Unit => TokenStream::new(),
+            syn::Fields::Unnamed(_) => quote! { ( #( #init_list ),* ) },
+            syn::Fields::Named(_) => quote! { { #( #init_list ),* } },
+        };
+
+        let method = if self.is_struct {
+            Ident::new("new", Span::call_site())
+        } else {
+            method_ident(&to_snake_case(&variant.ident.to_string()))
+        };
+        let doc = format!("Creates `{}` error", variant.ident);
+        let (track_caller, body) = self.impl_trace(variant, quote! { #item_id #init });
+        self.constructor_list.extend(quote! {
+            #cfg
+            #[doc = #doc]
+            #[inline]
+            #track_caller
+            pub fn #method( #( #arg_list: #ty_list ),* ) -> Self {
+                #body
+            }
+        });
+    }
+
+    fn impl_error_accessors(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let name = to_snake_case(&variant.ident.to_string());
+        let is_method = method_ident(&format!("is_{}", name));
+        let doc = format!("Returns `true` if error is `{}`", variant.ident);
+        self.accessor_list.extend(quote! {
+            #cfg
+            #[doc = #doc]
+            #[inline]
+            pub fn #is_method(&self) -> bool {
+                matches!(self, #item_id { .. })
+            }
+        });
+
+        if variant.fields.is_empty() {
+            return
+        }
+
+        let pattern = impl_fields_pattern(&variant.fields);
+        let arg_list: Vec<Ident> = variant.fields.iter().enumerate()
+            .map(|(i, v)| match &v.ident {
+                Some(v) => v.clone(),
+                None => Ident::new(&format!("i{}", i), Span::call_site()),
+            })
+            .collect();
+        let ty_list: Vec<&syn::Type> = variant.fields.iter().map(|v| &v.ty).collect();
+
+        let (ty, ty_mut, value) = if arg_list.len() == 1 {
+            let ty = ty_list[0];
+            let arg = &arg_list[0];
+            (quote! { &#ty }, quote! { &mut #ty }, quote! { #arg })
+        } else {
+            (
+                quote! { ( #( &#ty_list ),* ) },
+                quote! { ( #( &mut #ty_list ),* ) },
+                quote! { ( #( #arg_list ),* ) },
+            )
+        };
+
+        let as_method = method_ident(&format!("as_{}", name));
+        let as_mut_method = method_ident(&format!("as_{}_mut", name));
+        let doc = format!("Returns fields of the `{}` variant", variant.ident);
+        let doc_mut = format!("Returns mutable fields of the `{}` variant", variant.ident);
+        self.accessor_list.extend(quote! {
+            #cfg
+            #[doc = #doc]
+            #[inline]
+            #[allow(unreachable_patterns)]
+            pub fn #as_method(&self) -> Option<#ty> {
+                match self {
+                    #item_id #pattern => Some(#value),
+                    _ => None,
+                }
+            }
+
+            #[doc = #doc_mut]
+            #[inline]
+            #[allow(unreachable_patterns)]
+            pub fn #as_mut_method(&mut self) -> Option<#ty_mut> {
+                match self {
+                    #item_id #pattern => Some(#value),
+                    _ => None,
+                }
+            }
+        });
+    }
+
+    fn impl_error_into(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let (source_idx, field) = source_field(&variant.fields).unwrap();
+        let member = match &field.ident {
+            Some(v) => syn::Member::Named(v.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(source_idx)),
+        };
+        let ty = &field.ty;
+
+        if self.is_struct {
+            self.accessor_list.extend(quote! {
+                #cfg
+                /// Returns source error
+                #[inline]
+                pub fn into_inner(self) -> #ty {
+                    self.#member
+                }
+            });
+            return
+        }
+
+        let name = to_snake_case(&variant.ident.to_string());
+        let method = method_ident(&format!("into_{}", name));
+        let doc = format!("Returns source error of the `{}` variant or error itself", variant.ident);
+        self.accessor_list.extend(quote! {
+            #cfg
+            #[doc = #doc]
+            #[inline]
+            #[allow(unreachable_patterns)]
+            pub fn #method(self) -> ::core::result::Result<#ty, Self> {
+                match self {
+                    #item_id { #member: source, .. } => Ok(source),
+                    error => Err(error),
+                }
+            }
+        });
+
+        if self.single_variant {
+            self.accessor_list.extend(quote! {
+                #cfg
+                /// Returns source error
+                #[inline]
+                pub fn into_inner(self) -> #ty {
+                    let #item_id { #member: source, .. } = self;
+                    source
+                }
+            });
+        }
+    }
+
+    fn impl_error_code(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        let lit = match meta {
+            syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Int(v), .. }) => v,
+            _ => return self.meta_mismatch(meta),
+        };
+        let code = match self.check(lit.base10_parse::<u32>()) {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Some((_, name, _)) = self.code_table.iter().find(|(v, _, _)| *v == code) {
+            let message = format!("error code {} already defined for {}", code, name);
+            self.error_list.push(syn::Error::new(lit.span(), message));
+        }
+
+        let name = variant.ident.to_string();
+        self.code_list.extend(quote! {
+            #cfg
+            #item_id { .. } => #code,
+        });
+        self.code_table.push((code, name, cfg));
+    }
+
+    fn impl_error_optional_source(&mut self,
+        item_id: &TokenStream,
+        index: usize,
+        field: &syn::Field)
+    {
+        let cfg = self.cfg.clone();
+        let member = match &field.ident {
+            Some(v) => syn::Member::Named(v.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(index)),
+        };
+        let ty = &field.ty;
+
+        let is_option = match ty {
+            syn::Type::Path(v) => v.path.segments.last().unwrap().ident == "Option",
+            _ => false,
+        };
+        let inner = match type_arg(ty) {
+            Some(v) if is_option => v,
+            _ => {
+                let message = "optional source should have Option type";
+                return self.error_list.push(syn::Error::new_spanned(ty, message))
+            }
+        };
+
+        let source = self.source_expr(inner, quote! { source });
+        self.source_list.extend(quote! {
+            #cfg
+            #item_id { #member: Some(source), .. } => Some(#source),
+        });
+        self.source_type_list.push(ty.clone());
+    }
+
+    fn impl_error_category(&mut self,
+        item_id: &TokenStream,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        let category = match meta {
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.get_ident().is_some() => v.get_ident().unwrap().clone(),
+                _ => return self.meta_mismatch(meta),
+            },
+            _ => return self.meta_mismatch(meta),
+        };
+
+        let category_id = Ident::new(&format!("{}Category", self.enum_id), Span::call_site());
+        self.category_list.extend(quote! {
+            #cfg
+            #item_id { .. } => Some(#category_id::#category),
+        });
+        if ! self.category_names.contains(&category) {
+            self.category_names.push(category);
+        }
+    }
+
+    fn impl_error_transient(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        match meta {
+            syn::Meta::Path(_) => {
+                self.transient_list.extend(quote! {
+                    #cfg
+                    #item_id { .. } => true,
+                });
+            }
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("source") => {
+                    let (source_idx, field) = match source_field(&variant.fields) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            self.error_list.push(e);
+                            return
+                        }
+                    };
+                    let member = match &field.ident {
+                        Some(v) => syn::Member::Named(v.clone()),
+                        None => syn::Member::Unnamed(syn::Index::from(source_idx)),
+                    };
+                    self.transient_list.extend(quote! {
+                        #cfg
+                        #item_id { #member: source, .. } => source.is_transient(),
+                    });
+                }
+                _ => self.meta_mismatch(meta),
+            },
+            _ => self.meta_mismatch(meta),
+        }
+    }
+
+    fn impl_error_status(&mut self,
+        item_id: &TokenStream,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        let lit = match meta {
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Lit(syn::Lit::Int(v)) => v,
+                _ => return self.meta_mismatch(meta),
+            },
+            _ => return self.meta_mismatch(meta),
+        };
+
+        match lit.base10_parse::<u16>() {
+            Ok(status) if (100 ..= 599).contains(&status) => self.status_list.extend(quote! {
+                #cfg
+                #item_id { .. } => #status,
+            }),
+            _ => {
+                let message = "status should be in range 100..=599";
+                self.error_list.push(syn::Error::new(lit.span(), message));
+            }
+        }
+    }
+
+    fn impl_error_exit_code(&mut self,
+        item_id: &TokenStream,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        let lit = match meta {
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Lit(syn::Lit::Int(v)) => v,
+                _ => return self.meta_mismatch(meta),
+            },
+            _ => return self.meta_mismatch(meta),
+        };
+
+        match lit.base10_parse::<u8>() {
+            Ok(code) => self.exit_code_list.extend(quote! {
+                #cfg
+                #item_id { .. } => ::std::process::ExitCode::from(#code),
+            }),
+            Err(_) => {
+                let message = "exit code should be in range 0..=255";
+                self.error_list.push(syn::Error::new(lit.span(), message));
+            }
+        }
+    }
+
+    fn impl_error_severity(&mut self,
+        item_id: &TokenStream,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        let level = match meta {
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.get_ident().is_some() => v.get_ident().unwrap().clone(),
+                _ => return self.meta_mismatch(meta),
+            },
+            _ => return self.meta_mismatch(meta),
+        };
+
+        let id = match level.to_string().as_str() {
+            "trace" => "Trace",
+            "debug" => "Debug",
+            "warn" => "Warn",
+            "error" => "Error",
+            "fatal" => "Fatal",
+            _ => {
+                let message = "severity should be one of: trace, debug, warn, error, fatal";
+                self.error_list.push(syn::Error::new(level.span(), message));
+                return
+            }
+        };
+
+        let severity_id = Ident::new(&format!("{}Severity", self.enum_id), Span::call_site());
+        let id = Ident::new(id, Span::call_site());
+        self.severity_list.extend(quote! {
+            #cfg
+            #item_id { .. } => #severity_id::#id,
+        });
+    }
+
+    fn impl_error_builder(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let fields = match &variant.fields {
+            syn::Fields::Named(v) => &v.named,
+            _ => {
+                let message = "error_builder required named fields";
+                return self.error_list.push(syn::Error::new_spanned(&variant.ident, message))
+            }
+        };
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let vis = &self.vis;
+        let generics = &self.generics;
+
+        let (builder_id, method) = if self.is_struct {
+            (format!("{}Builder", enum_id), "builder".to_owned())
+        } else {
+            (format!("{}{}Builder", enum_id, variant.ident), to_snake_case(&variant.ident.to_string()))
+        };
+        let builder_name = builder_id.clone();
+        let builder_id = Ident::new(&builder_id, Span::call_site());
+        let missing_id = Ident::new(&format!("{}MissingField", enum_id), Span::call_site());
+        let method = method_ident(&method);
+        let builder_ty = {
+            let (_, ty_generics, _) = self.generics.split_for_impl();
+            quote! { #builder_id #ty_generics }
+        };
+
+        let mut field_list = TokenStream::new();
+        let mut init_list = TokenStream::new();
+        let mut setter_list = TokenStream::new();
+        let mut build_list = TokenStream::new();
+
+        for field in fields {
+            let id = field.ident.as_ref().unwrap();
+            let ty = &field.ty;
+            field_list.extend(quote! { #id: Option<#ty>, });
+            init_list.extend(quote! { #id: None, });
+            setter_list.extend(quote! {
+                #[inline]
+                pub fn #id(mut self, value: #ty) -> Self {
+                    self.#id = Some(value);
+                    self
+                }
+            });
+
+            let is_option = match ty {
+                syn::Type::Path(v) => v.path.segments.last().unwrap().ident == "Option",
+                _ => false,
+            };
+            if let Some(capture) = field_capture(field) {
+                build_list.extend(quote! { #id: self.#id.unwrap_or_else(|| #capture), });
+            } else if is_option {
+                build_list.extend(quote! { #id: self.#id.unwrap_or(None), });
+            } else {
+                let field_name = id.to_string();
+                build_list.extend(quote! {
+                    #id: match self.#id {
+                        Some(v) => v,
+                        None => return Err(#missing_id {
+                            builder: #builder_name,
+                            field: #field_name,
+                        }),
+                    },
+                });
+            }
+        }
+
+        let doc = format!("Builder for the `{}` variant", variant.ident);
+        let (track_caller, body) = self.impl_trace(variant, quote! { #item_id { #build_list } });
+        self.builder_list.extend(quote! {
+            #cfg
+            #[doc = #doc]
+            #vis struct #builder_id #generics #where_clause {
+                #field_list
+                _marker: ::core::marker::PhantomData<#enum_ty>,
+            }
+
+            #cfg
+            impl #impl_generics #builder_ty #where_clause {
+                #setter_list
+
+                /// Returns error or `Err` with the first required field which
+                /// is not defined. Fields with `Option` type are not required
+                #track_caller
+                pub fn build(self) -> ::core::result::Result<#enum_ty, #missing_id> {
+                    Ok({ #body })
+                }
+            }
+
+            #cfg
+            impl #impl_generics #enum_ty #where_clause {
+                #[doc = #doc]
+                pub fn #method() -> #builder_ty {
+                    #builder_id {
+                        #init_list
+                        _marker: ::core::marker::PhantomData,
+                    }
+                }
+            }
+        });
+    }
+
+    fn impl_error_group(&mut self,
+        item_id: &TokenStream,
+        meta: &syn::Meta)
+    {
+        let cfg = self.cfg.clone();
+        if let syn::Meta::List(v) = meta {
+            if v.nested.len() == 1 {
+                if let syn::NestedMeta::Lit(syn::Lit::Str(v)) = &v.nested[0] {
+                    let group = v.value();
+                    self.group_list.extend(quote! {
+                        #cfg
+                        #item_id { .. } => Some(#group),
+                    });
+                    return
+                }
+            }
+        }
+        self.meta_mismatch(meta)
+    }
+
+    fn impl_error_deadline(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant,
+        meta: &syn::MetaList)
+    {
+        let cfg = self.cfg.clone();
+        match meta.nested.iter().next() {
+            Some(syn::NestedMeta::Meta(syn::Meta::Path(v))) if meta.nested.len() == 1 && v.is_ident("deadline") => {}
+            _ => return self.meta_mismatch(meta),
+        }
+
+        let has_field = variant.fields.iter()
+            .filter_map(|f| f.ident.as_ref())
+            .any(|v| v == "deadline");
+        if ! has_field {
+            let message = "error_timeout(deadline) required field deadline";
+            self.error_list.push(syn::Error::new(variant.ident.span(), message));
+            return
+        }
+
+        self.deadline_list.extend(quote! {
+            #cfg
+            #item_id { deadline, .. } => *deadline = timeout,
+        });
+    }
+
+    /// Generics of the extension trait for `Result<__T, __E>`:
+    /// trait parameters, trait type generics, and impl generics
+    fn result_ext_generics(&self) -> (TokenStream, TokenStream, TokenStream) {
+        let mut trait_generics = self.generics.clone();
+        trait_generics.params.push(syn::parse_quote! { __T });
+        let (_, trait_ty, _) = trait_generics.split_for_impl();
+        let mut generics = trait_generics.clone();
+        generics.params.push(syn::parse_quote! { __E });
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let trait_params = &trait_generics.params;
+
+        (quote! { #trait_params }, quote! { #trait_ty }, quote! { #impl_generics })
+    }
+
+    fn impl_error_example(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let init = match &variant.fields {
+            syn::Fields::Unit => TokenStream::new(),
+            _ if ! variant.attrs.iter().any(|v| v.path.is_ident("error_example")) => return,
+            syn::Fields::Named(fields) => {
+                let id_list = fields.named.iter().map(|v| &v.ident);
+                quote! { { #( #id_list: Default::default(), )* } }
+            }
+            syn::Fields::Unnamed(fields) => {
+                let value_list = fields.unnamed.iter().map(|_| quote! { Default::default() });
+                quote! { ( #( #value_list, )* ) }
+            }
+        };
+        self.example_list.extend(quote! {
+            #cfg
+            list.push(#item_id #init);
+        });
+    }
+
+    fn impl_error_or_kind(&mut self,
+        item_id: &TokenStream,
+        index: usize,
+        field: &syn::Field)
+    {
+        // only `Option<Box<dyn Error>>` could hold any error
+        if ! matches!(type_arg(&field.ty), Some(ty) if is_boxed_dyn(ty)) {
+            return
+        }
+
+        let cfg = self.cfg.clone();
+        let member = match &field.ident {
+            Some(v) => syn::Member::Named(v.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(index)),
+        };
+        self.or_kind_list.extend(quote! {
+            #cfg
+            #item_id { #member: source, .. } => *source = Some(Box::new(e)),
+        });
+    }
+
+    fn impl_error_or_kind_ext(&self) -> TokenStream {
+        if ! self.or_kind {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let vis = &self.vis;
+        let (_, enum_ty, _) = self.split_generics();
+        let ext_id = Ident::new(&format!("{}OrKindExt", enum_id), Span::call_site());
+        let doc = format!("Replaces error with the `{}` variant, original error kept as source", enum_id);
+
+        let (trait_params, trait_ty, impl_generics) = self.result_ext_generics();
+        let trait_where = &self.generics.where_clause;
+        let or_kind_where = self.where_with(quote! {
+            __E: ::std::error::Error + Send + Sync + 'static,
+        });
+        let or_kind_list = &self.or_kind_list;
+
+        quote! {
+            #[doc = #doc]
+            #vis trait #ext_id<#trait_params> #trait_where {
+                /// Replaces error with the given variant
+                fn or_kind(self, kind: #enum_ty) -> ::core::result::Result<__T, #enum_ty>;
+
+                /// Replaces error with the variant returned by the closure
+                fn with_kind<F>(self, f: F) -> ::core::result::Result<__T, #enum_ty>
+                where
+                    F: FnOnce() -> #enum_ty;
+            }
+
+            impl #impl_generics #ext_id #trait_ty for ::core::result::Result<__T, __E> #or_kind_where {
+                #[inline]
+                fn or_kind(self, kind: #enum_ty) -> ::core::result::Result<__T, #enum_ty> {
+                    self.with_kind(|| kind)
+                }
+
+                fn with_kind<F>(self, f: F) -> ::core::result::Result<__T, #enum_ty>
+                where
+                    F: FnOnce() -> #enum_ty,
+                {
+                    self.map_err(|e| {
+                        let mut error = f();
+                        #[allow(unreachable_patterns)]
+                        match &mut error {
+                            #or_kind_list
+                            _ => {}
+                        }
+                        error
+                    })
+                }
+            }
+        }
+    }
+
+    fn impl_error_sink_ext(&self) -> TokenStream {
+        if ! self.sink {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let vis = &self.vis;
+        let (_, enum_ty, _) = self.split_generics();
+        let ext_id = Ident::new(&format!("{}SinkExt", enum_id), Span::call_site());
+        let doc = format!("Sends error converted into `{}` to the channel", enum_id);
+
+        let (trait_params, trait_ty, impl_generics) = self.result_ext_generics();
+        let trait_where = &self.generics.where_clause;
+        let sink_where = self.where_with(quote! {
+            __E: Into<#enum_ty>,
+        });
+
+        quote! {
+            #[doc = #doc]
+            #vis trait #ext_id<#trait_params> #trait_where {
+                /// Returns value or sends error to the sink and returns `None`.
+                /// Error is dropped if the receiver is closed
+                fn or_report(self, sink: &::std::sync::mpsc::Sender<#enum_ty>) -> Option<__T>;
+            }
+
+            impl #impl_generics #ext_id #trait_ty for ::core::result::Result<__T, __E> #sink_where {
+                fn or_report(self, sink: &::std::sync::mpsc::Sender<#enum_ty>) -> Option<__T> {
+                    match self {
+                        Ok(v) => Some(v),
+                        Err(e) => {
+                            let _ = sink.send(e.into());
+                            None
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_error_deadline_ext(&self) -> TokenStream {
+        if self.deadline_list.is_empty() {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let vis = &self.vis;
+        let (_, enum_ty, _) = self.split_generics();
+        let ext_id = Ident::new(&format!("{}DeadlineExt", enum_id), Span::call_site());
+        let doc = format!("Converts error into `{}` and attaches timeout to the `deadline` field", enum_id);
+
+        let (trait_params, trait_ty, impl_generics) = self.result_ext_generics();
+        let trait_where = &self.generics.where_clause;
+        let deadline_where = self.where_with(quote! {
+            __E: Into<#enum_ty>,
+        });
+        let deadline_list = &self.deadline_list;
+
+        quote! {
+            #[doc = #doc]
+            #vis trait #ext_id<#trait_params> #trait_where {
+                /// Converts error and sets `deadline` field of the timeout variants
+                fn deadline(self, timeout: ::core::time::Duration) -> ::core::result::Result<__T, #enum_ty>;
+            }
+
+            impl #impl_generics #ext_id #trait_ty for ::core::result::Result<__T, __E> #deadline_where {
+                fn deadline(self, timeout: ::core::time::Duration) -> ::core::result::Result<__T, #enum_ty> {
+                    self.map_err(|e| {
+                        let mut error: #enum_ty = e.into();
+                        #[allow(unreachable_patterns)]
+                        match &mut error {
+                            #deadline_list
+                            _ => {}
+                        }
+                        error
+                    })
+                }
+            }
+        }
+    }
+
+    fn impl_error_format(&mut self,
+        item_id: &TokenStream,
+        variant: &syn::Variant)
+    {
+        let cfg = self.cfg.clone();
+        let (fields, ident_list) = match &variant.fields {
+            syn::Fields::Unit => (String::new(), Vec::new()),
+            syn::Fields::Unnamed(fields) => {
+                let ident_list: Vec<Ident> = (0 .. fields.unnamed.len())
+                    .map(|i| Ident::new(&format!("i{}", i), Span::call_site()))
+                    .collect();
+                (vec!["{}"; ident_list.len()].join(", "), ident_list)
+            }
+            syn::Fields::Named(fields) => {
+                let ident_list: Vec<Ident> = fields.named.iter()
+                    .map(|v| v.ident.clone().unwrap())
+                    .collect();
+                let fields = ident_list.iter()
+                    .map(|v| format!("{}: {{}}", v))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                (fields, ident_list)
+            }
+        };
+
+        let format = self.format.as_ref().unwrap().value();
+        let fmt = match expand_format(&format, &variant.ident.to_string(), &fields) {
+            Ok(v) => v,
+            Err(_) => return,
+        };
+
+        let mut prefix = TokenStream::new();
+        if fmt_has_named(&fmt, "error_rules_prefix") {
+            let value = self.prefix_expr();
+            prefix.extend(quote! { , error_rules_prefix = #value });
+            self.format_prefix_list.extend(quote! {
+                #cfg
+                #item_id { .. } => true,
+            });
+        }
+
+        if fmt_has_named(&format, "fields") {
+            let pattern = impl_fields_pattern(&variant.fields);
+            self.display_list.extend(quote! {
+                #cfg
+                #item_id #pattern => write!(f, #fmt #( , #ident_list )* #prefix),
+            });
+        } else {
+            self.display_list.extend(quote! {
+                #cfg
+                #item_id { .. } => write!(f, #fmt #prefix),
+            });
+        }
+    }
+
+    fn impl_crash_report(&self) -> TokenStream {
+        if ! self.crash_report {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+
+        quote! {
+            impl #impl_generics #enum_ty #where_clause {
+                /// Writes report with the error chain, environment, backtrace and
+                /// additional attachments into the temporary directory.
+                /// Returns path to the report file.
+                pub fn write_crash_report(&self, attachments: &[(&str, &str)])
+                    -> ::std::io::Result<::std::path::PathBuf>
+                where
+                    Self: ::std::error::Error,
+                {
+                    use ::std::io::Write;
+
+                    let time = ::std::time::SystemTime::now()
+                        .duration_since(::std::time::UNIX_EPOCH)
+                        .map(|v| v.as_secs())
+                        .unwrap_or_default();
+                    let path = ::std::env::temp_dir().join(format!("{}-report-{}-{}.txt",
+                        env!("CARGO_PKG_NAME"),
+                        ::std::process::id(),
+                        time));
+
+                    let mut file = ::std::fs::File::create(&path)?;
+                    writeln!(file, "name: {}", env!("CARGO_PKG_NAME"))?;
+                    writeln!(file, "version: {}", env!("CARGO_PKG_VERSION"))?;
+                    writeln!(file, "os: {} {}", ::std::env::consts::OS, ::std::env::consts::ARCH)?;
+                    writeln!(file)?;
+
+                    writeln!(file, "error: {}", self)?;
+                    let mut source = ::std::error::Error::source(self);
+                    while let Some(e) = source {
+                        writeln!(file, "caused by: {}", e)?;
+                        source = e.source();
+                    }
+
+                    for (name, value) in attachments {
+                        writeln!(file)?;
+                        writeln!(file, "{}:", name)?;
+                        writeln!(file, "{}", value)?;
+                    }
+
+                    writeln!(file)?;
+                    writeln!(file, "backtrace:")?;
+                    writeln!(file, "{}", ::std::backtrace::Backtrace::force_capture())?;
+
+                    Ok(path)
+                }
+
+                /// Prints error and saves crash report.
+                /// Prints path to the report file on success.
+                pub fn crash_report(&self, attachments: &[(&str, &str)])
+                where
+                    Self: ::std::error::Error,
+                {
+                    eprintln!("Error: {}", self);
+                    match self.write_crash_report(attachments) {
+                        Ok(path) => eprintln!("Crash report saved to {}", path.display()),
+                        Err(e) => eprintln!("Failed to save crash report: {}", e),
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_result_alias(&self) -> TokenStream {
+        let result_id = match &self.result {
+            Some(v) => Ident::new(v, Span::call_site()),
+            None => return TokenStream::new(),
+        };
+
+        let enum_id = &self.enum_id;
+        let (_, enum_ty, _) = self.split_generics();
+        let vis = &self.vis;
+        // lifetimes should be declared before the value type
+        let lifetime_list = self.generics.lifetimes().map(|v| &v.lifetime);
+        let param_list = self.generics.params.iter().filter_map(|v| match v {
+            syn::GenericParam::Type(v) => {
+                let id = &v.ident;
+                Some(quote! { #id })
+            }
+            syn::GenericParam::Lifetime(_) => None,
+            syn::GenericParam::Const(v) => {
+                let id = &v.ident;
+                let ty = &v.ty;
+                Some(quote! { const #id: #ty })
+            }
+        });
+        let doc = format!("Result with `{}` error", enum_id);
+
+        quote! {
+            #[doc = #doc]
+            #vis type #result_id<#( #lifetime_list , )* __T #( , #param_list )*> =
+                ::core::result::Result<__T, #enum_ty>;
+        }
+    }
+
+    fn impl_code(&mut self) -> TokenStream {
+        if self.code_table.is_empty() {
+            return TokenStream::new()
+        }
+
+        for variant_id in &self.code_missing_list {
+            let message = format!("error_code required for {}", variant_id);
+            self.error_list.push(syn::Error::new(variant_id.span(), message));
+        }
+
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let code_list = &self.code_list;
+        let code_table = self.code_table.iter().map(|(code, name, cfg)| quote! { #cfg (#code, #name), });
+
+        quote! {
+            impl #impl_generics #enum_ty #where_clause {
+                /// List of the error codes and variant names
+                pub const CODES: &'static [(u32, &'static str)] = &[
+                    #( #code_table )*
+                ];
+
+                /// Returns error code defined with `#[error_code = N]`
+                pub fn code(&self) -> u32 {
+                    match self {
+                        #code_list
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_kind_enum(&self) -> TokenStream {
+        if ! self.kind_enum {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let vis = &self.vis;
+        let kind_id = Ident::new(&format!("{}Kind", enum_id), Span::call_site());
+        let kind_list = &self.kind_list;
+        let kind_match_list = &self.kind_match_list;
+        let doc = format!("Variants of the `{}` without fields", enum_id);
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #kind_id {
+                #kind_list
+            }
+
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns variant of the error without fields
+                pub fn kind(&self) -> #kind_id {
+                    match self {
+                        #kind_match_list
+                    }
+                }
+            }
+
+            impl #impl_generics PartialEq<#kind_id> for #enum_ty #where_clause {
+                #[inline]
+                fn eq(&self, other: &#kind_id) -> bool {
+                    self.kind() == *other
+                }
+            }
+        }
+    }
+
+    fn impl_exit_code(&self) -> TokenStream {
+        if self.exit_code_list.is_empty() && ! self.termination {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let exit_code_list = &self.exit_code_list;
+
+        let mut result = quote! {
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns process exit code defined with `#[error_exit_code(N)]`.
+                /// Variants without attribute have `ExitCode::FAILURE`
+                pub fn exit_code(&self) -> ::std::process::ExitCode {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #exit_code_list
+                        _ => ::std::process::ExitCode::FAILURE,
+                    }
+                }
+            }
+        };
+
+        if self.termination {
+            let vis = &self.vis;
+            let generics = &self.generics;
+            let exit_id = Ident::new(&format!("{}Exit", enum_id), Span::call_site());
+            let exit_ty = {
+                let (_, ty_generics, _) = self.generics.split_for_impl();
+                quote! { #exit_id #ty_generics }
+            };
+            let doc = format!("Result of the `main()` with `{}` error", enum_id);
+            let exit_where = self.where_with(quote! {
+                #enum_ty: ::core::fmt::Display,
+            });
+
+            result.extend(quote! {
+                #[doc = #doc]
+                #vis struct #exit_id #generics (pub ::core::result::Result<(), #enum_ty>) #where_clause;
+
+                impl #impl_generics From<::core::result::Result<(), #enum_ty>> for #exit_ty #where_clause {
+                    #[inline]
+                    fn from(result: ::core::result::Result<(), #enum_ty>) -> Self {
+                        #exit_id(result)
+                    }
+                }
+
+                impl #impl_generics ::std::process::Termination for #exit_ty #exit_where {
+                    fn report(self) -> ::std::process::ExitCode {
+                        match self.0 {
+                            Ok(()) => ::std::process::ExitCode::SUCCESS,
+                            Err(e) => {
+                                eprintln!("Error: {:#}", e);
+                                e.exit_code()
+                            }
+                        }
+                    }
+                }
+            });
+        }
+
+        result
+    }
+
+    fn impl_category(&self) -> TokenStream {
+        if self.category_names.is_empty() {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let vis = &self.vis;
+        let category_id = Ident::new(&format!("{}Category", enum_id), Span::call_site());
+        let category_names = &self.category_names;
+        let category_list = &self.category_list;
+        let doc = format!("Categories of the `{}` defined with `#[error_category]`", enum_id);
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #category_id {
+                #( #category_names, )*
+            }
+
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns category of the variant defined with `#[error_category]`
+                pub fn category(&self) -> Option<#category_id> {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #category_list
+                        _ => None,
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_status(&self) -> TokenStream {
+        let problem_json = self.problem_json && ! self.no_std;
+        if self.status_list.is_empty() && ! problem_json {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let status_list = &self.status_list;
+
+        let variant_name = self.variant_name_expr(quote! { self });
+        let mut result = quote! {
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns HTTP status code defined with `#[error_status(N)]`.
+                /// Variants without attribute have `500` status
+                pub fn status(&self) -> u16 {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #status_list
+                        _ => 500,
+                    }
+                }
+            }
+        };
+
+        if problem_json {
+            let url = if self.url_list.is_empty() {
+                quote! { None }
+            } else {
+                quote! { self.url() }
+            };
+            let mut code = TokenStream::new();
+            if ! self.code_table.is_empty() {
+                code.extend(quote! {
+                    json.push_str(&format!(",\"code\":{}", self.code()));
+                });
+            }
+
+            result.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns RFC 7807 problem document with `type` from `#[error_url]`,
+                    /// variant name as `title`, `status`, message as `detail` and
+                    /// `instance` if defined
+                    pub fn problem_json(&self, instance: Option<&str>) -> String
+                    where
+                        Self: ::core::fmt::Display,
+                    {
+                        fn escape(text: &str) -> String {
+                            let mut result = String::with_capacity(text.len());
+                            for c in text.chars() {
+                                match c {
+                                    '"' => result.push_str("\\\""),
+                                    '\\' => result.push_str("\\\\"),
+                                    '\n' => result.push_str("\\n"),
+                                    '\r' => result.push_str("\\r"),
+                                    '\t' => result.push_str("\\t"),
+                                    c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+                                    c => result.push(c),
+                                }
+                            }
+                            result
+                        }
+
+                        let url: Option<&str> = #url;
+                        let mut json = String::new();
+                        json.push_str(&format!("{{\"type\":\"{}\"", escape(url.unwrap_or("about:blank"))));
+                        json.push_str(&format!(",\"title\":\"{}\"", #variant_name));
+                        json.push_str(&format!(",\"status\":{}", self.status()));
+                        json.push_str(&format!(",\"detail\":\"{}\"", escape(&self.to_string())));
+                        if let Some(instance) = instance {
+                            json.push_str(&format!(",\"instance\":\"{}\"", escape(instance)));
+                        }
+                        #code
+                        json.push('}');
+                        json
+                    }
+                }
+            });
+        }
+
+        result
+    }
+
+    fn impl_ffi(&mut self) -> TokenStream {
+        if ! self.ffi {
+            return TokenStream::new()
+        }
+        if self.code_table.is_empty() {
+            let message = "error_ffi required error_code for each variant";
+            self.error_list.push(syn::Error::new(self.enum_id.span(), message));
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let vis = &self.vis;
+        let code_id = Ident::new(&format!("{}Code", enum_id), Span::call_site());
+        let doc = format!("Codes of the `{}` for the C API", enum_id);
+
+        let mut code_list = TokenStream::new();
+        let mut code_match_list = TokenStream::new();
+        for (code, name, cfg) in &self.code_table {
+            let variant_id = Ident::new(name, Span::call_site());
+            if *code > i32::MAX as u32 {
+                let message = format!("error code {} of {} does not fit into i32", code, name);
+                self.error_list.push(syn::Error::new(enum_id.span(), message));
+                continue
+            }
+            let code = proc_macro2::Literal::i32_unsuffixed(*code as i32);
+            let item_id = if self.is_struct {
+                quote! { #enum_id }
+            } else {
+                quote! { #enum_id::#variant_id }
+            };
+            code_list.extend(quote! {
+                #cfg
+                #variant_id = #code,
+            });
+            code_match_list.extend(quote! {
+                #cfg
+                #item_id { .. } => #code_id::#variant_id,
+            });
+        }
+
+        quote! {
+            #[doc = #doc]
+            #[repr(C)]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #code_id {
+                #code_list
+            }
+
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns code of the error for the C API
+                pub fn ffi_code(&self) -> #code_id {
+                    match self {
+                        #code_match_list
+                    }
+                }
+
+                /// Returns code and message of the error for the C API.
+                /// NUL characters are removed from the message
+                pub fn to_ffi(&self) -> (i32, ::std::ffi::CString)
+                where
+                    Self: ::core::fmt::Display,
+                {
+                    let message = self.to_string().replace('\0', "");
+                    let message = ::std::ffi::CString::new(message).unwrap_or_default();
+                    (self.ffi_code() as i32, message)
+                }
+            }
+        }
+    }
+
+    fn impl_severity(&self) -> TokenStream {
+        if self.severity_list.is_empty() && ! self.syslog {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let vis = &self.vis;
+        let severity_id = Ident::new(&format!("{}Severity", enum_id), Span::call_site());
+        let severity_list = &self.severity_list;
+        let doc = format!("Severity level of the `{}`", enum_id);
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #vis enum #severity_id {
+                Trace,
+                Debug,
+                Warn,
+                Error,
+                Fatal,
+            }
+
+            impl #severity_id {
+                /// Returns lowercase name of the level
+                pub fn as_str(&self) -> &'static str {
+                    match self {
+                        #severity_id::Trace => "trace",
+                        #severity_id::Debug => "debug",
+                        #severity_id::Warn => "warn",
+                        #severity_id::Error => "error",
+                        #severity_id::Fatal => "fatal",
+                    }
+                }
+            }
+
+            impl ::core::fmt::Display for #severity_id {
+                #[inline]
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns severity defined with `#[error_severity(level)]`.
+                /// Variants without attribute have `Error` level
+                pub fn severity(&self) -> #severity_id {
+                    #[allow(unreachable_patterns)]
+                    match self {
+                        #severity_list
+                        _ => #severity_id::Error,
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_serialize(&self) -> TokenStream {
+        if ! self.serialize {
+            return TokenStream::new()
+        }
+
+        let enum_name = self.enum_id.to_string();
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let serialize_where = self.where_with(quote! {
+            #enum_ty: ::std::error::Error + 'static,
+        });
+
+        let serialize_fields_match_list = &self.serialize_fields_match_list;
+        let mut variant_name = self.variant_name_expr(quote! { self });
+        if ! self.wire_list.is_empty() {
+            let wire_list = &self.wire_list;
+            variant_name = quote! {
+                match self {
+                    #wire_list
+                    _ => #variant_name,
+                }
+            };
+        }
+
+        let mut wire_since = TokenStream::new();
+        if ! self.wire_since_list.is_empty() {
+            let wire_since_list = &self.wire_since_list;
+            let (_, _, where_clause) = self.split_generics();
+            wire_since = quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns version of the wire format defined with `#[error_wire(since = "...")]`
+                    pub fn wire_since(&self) -> Option<&'static str> {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #wire_since_list
+                            _ => None,
+                        }
+                    }
+                }
+            };
+        }
+        let mut field_count = 3usize;
+        let mut code = TokenStream::new();
+        if ! self.code_table.is_empty() {
+            field_count += 1;
+            code.extend(quote! {
+                state.serialize_field("code", &self.code())?;
+            });
+        }
+
+        let mut fields = TokenStream::new();
+        if self.deserialize {
+            field_count += 1;
+            fields.extend(quote! {
+                let fields = match self {
+                    #serialize_fields_match_list
+                };
+                state.serialize_field("fields", &fields)?;
+            });
+        }
+
+        let serialize_impl = quote! {
+            impl #impl_generics serde::Serialize for #enum_ty #serialize_where {
+                fn serialize<__S>(&self, serializer: __S) -> ::core::result::Result<__S::Ok, __S::Error>
+                where
+                    __S: serde::Serializer,
+                {
+                    use serde::ser::SerializeStruct;
+
+                    let chain: Vec<String> = ::core::iter::successors(
+                        ::std::error::Error::source(self),
+                        |e| e.source())
+                        .map(|e| e.to_string())
+                        .collect();
+
+                    #[allow(unreachable_patterns)]
+                    let variant: &str = #variant_name;
+
+                    let mut state = serializer.serialize_struct(#enum_name, #field_count)?;
+                    state.serialize_field("variant", variant)?;
+                    state.serialize_field("message", &self.to_string())?;
+                    #code
+                    state.serialize_field("chain", &chain)?;
+                    #fields
+                    state.end()
+                }
+            }
+
+            #wire_since
+        };
+
+        if ! self.deserialize {
+            return serialize_impl
+        }
+
+        let deserialize_list = &self.deserialize_list;
+        let deserialize_from_list = &self.deserialize_from_list;
+        let serialize_fields_list = &self.serialize_fields_list;
+        let lifetime = if self.serialize_has_fields {
+            quote! { <'a> }
+        } else {
+            TokenStream::new()
+        };
+
+        quote! {
+            const _: () = {
+                #[derive(serde::Serialize)]
+                #[serde(untagged)]
+                enum __Fields #lifetime {
+                    #serialize_fields_list
+                }
+
+                #[derive(serde::Deserialize)]
+                #[serde(tag = "variant", content = "fields")]
+                enum __Repr {
+                    #deserialize_list
+                }
+
+                #serialize_impl
+
+                impl<'de> serde::Deserialize<'de> for #enum_ty {
+                    fn deserialize<__D>(deserializer: __D) -> ::core::result::Result<Self, __D::Error>
+                    where
+                        __D: serde::Deserializer<'de>,
+                    {
+                        let repr = <__Repr as serde::Deserialize>::deserialize(deserializer)?;
+                        Ok(match repr {
+                            #deserialize_from_list
+                        })
+                    }
+                }
+            };
+        }
+    }
+
+    fn impl_error_stack(&self) -> TokenStream {
+        if ! self.report {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+
+        let mut attach_list = TokenStream::new();
+        if ! self.code_table.is_empty() {
+            attach_list.extend(quote! {
+                let code = format!("code: {}", self.code());
+            });
+        }
+        if ! self.help_list.is_empty() {
+            attach_list.extend(quote! {
+                let help = self.help().map(|v| format!("help: {}", v));
+            });
+        }
+        if ! self.url_list.is_empty() {
+            attach_list.extend(quote! {
+                let url = self.url().map(|v| format!("see: {}", v));
+            });
+        }
+
+        let mut report_list = TokenStream::new();
+        if ! self.code_table.is_empty() {
+            report_list.extend(quote! {
+                report = report.attach_printable(code);
+            });
+        }
+        if ! self.help_list.is_empty() {
+            report_list.extend(quote! {
+                if let Some(help) = help {
+                    report = report.attach_printable(help);
+                }
+            });
+        }
+        if ! self.url_list.is_empty() {
+            report_list.extend(quote! {
+                if let Some(url) = url {
+                    report = report.attach_printable(url);
+                }
+            });
+        }
+
+        quote! {
+            impl #impl_generics #enum_ty #where_clause {
+                /// Converts error into `error_stack::Report` with code, help
+                /// and documentation link attachments
+                pub fn into_report(self) -> error_stack::Report<Self>
+                where
+                    Self: ::std::error::Error + Send + Sync + 'static,
+                {
+                    #attach_list
+                    #[allow(unused_mut)]
+                    let mut report = error_stack::Report::new(self);
+                    #report_list
+                    report
+                }
+            }
+        }
+    }
+
+    fn impl_wasm(&self) -> TokenStream {
+        if ! self.wasm {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let wasm_where = self.where_with(quote! {
+            #enum_ty: ::std::error::Error + 'static,
+        });
+
+        let variant_name = self.variant_name_expr(quote! { &error });
+        let mut code = TokenStream::new();
+        if ! self.code_table.is_empty() {
+            code.extend(quote! {
+                set("code", wasm_bindgen::JsValue::from(error.code()));
+            });
+        }
+
+        quote! {
+            impl #impl_generics From<#enum_ty> for wasm_bindgen::JsValue #wasm_where {
+                fn from(error: #enum_ty) -> Self {
+                    let object = js_sys::Object::new();
+                    let set = |key: &str, value: wasm_bindgen::JsValue| {
+                        let _ = js_sys::Reflect::set(&object, &wasm_bindgen::JsValue::from_str(key), &value);
+                    };
+
+                    set("variant", wasm_bindgen::JsValue::from_str(#variant_name));
+                    set("message", wasm_bindgen::JsValue::from_str(&error.to_string()));
+                    #code
+
+                    let chain = js_sys::Array::new();
+                    let mut source = ::std::error::Error::source(&error);
+                    while let Some(e) = source {
+                        chain.push(&wasm_bindgen::JsValue::from_str(&e.to_string()));
+                        source = e.source();
+                    }
+                    set("chain", chain.into());
+
+                    object.into()
+                }
+            }
+        }
+    }
+
+    fn impl_axum(&self) -> TokenStream {
+        if ! self.axum {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let axum_where = self.where_with(quote! {
+            #enum_ty: ::core::fmt::Display,
+        });
+
+        quote! {
+            impl #impl_generics axum::response::IntoResponse for #enum_ty #axum_where {
+                fn into_response(self) -> axum::response::Response {
+                    let status = axum::http::StatusCode::from_u16(self.status())
+                        .unwrap_or(axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+                    let headers = [(axum::http::header::CONTENT_TYPE, "application/problem+json")];
+                    (status, headers, self.problem_json(None)).into_response()
+                }
+            }
+        }
+    }
+
+    fn impl_tonic(&self) -> TokenStream {
+        if ! self.tonic {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let tonic_where = self.where_with(quote! {
+            #enum_ty: ::core::fmt::Display,
+        });
+        let grpc_list = &self.grpc_list;
+        let grpc_default = match &self.grpc_default {
+            Some(v) => v.clone(),
+            None => Ident::new("Unknown", Span::call_site()),
+        };
+
+        quote! {
+            impl #impl_generics From<#enum_ty> for tonic::Status #tonic_where {
+                fn from(error: #enum_ty) -> Self {
+                    #[allow(unreachable_patterns)]
+                    let code = match &error {
+                        #grpc_list
+                        _ => tonic::Code::#grpc_default,
+                    };
+                    tonic::Status::new(code, error.to_string())
+                }
+            }
+        }
+    }
+
+    fn impl_pyo3(&self) -> TokenStream {
+        if ! self.pyo3 {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let pyo3_where = self.where_with(quote! {
+            #enum_ty: ::core::fmt::Display,
+        });
+        let py_list = &self.py_list;
+
+        let py_default = match &self.py_default {
+            Some(v) => v.clone(),
+            None => quote! { pyo3::exceptions::PyRuntimeError },
+        };
+        let fallback = if self.py_category_list.is_empty() {
+            quote! { #py_default::new_err(message) }
+        } else {
+            let py_category_list = &self.py_category_list;
+            quote! {
+                match error.category() {
+                    #py_category_list
+                    _ => #py_default::new_err(message),
+                }
+            }
+        };
+
+        quote! {
+            impl #impl_generics From<#enum_ty> for pyo3::PyErr #pyo3_where {
+                fn from(error: #enum_ty) -> Self {
+                    let message = error.to_string();
+                    #[allow(unreachable_patterns)]
+                    match &error {
+                        #py_list
+                        _ => #fallback,
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_defmt(&self) -> TokenStream {
+        if ! self.defmt {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let defmt_list = &self.defmt_list;
+        let variant_name = self.variant_name_expr(quote! { self });
+
+        let mut defmt_prefix = TokenStream::new();
+        if self.prefix_module {
+            defmt_prefix.extend(quote! {
+                defmt::write!(f, "{=str}: ", module_path!().rsplit("::").next().unwrap_or_default());
+            });
+        } else if ! self.prefix.is_empty() {
+            let prefix = &self.prefix;
+            defmt_prefix.extend(quote! {
+                defmt::write!(f, "{=str}: ", #prefix);
+            });
+        }
+        if ! self.group_list.is_empty() {
+            defmt_prefix.extend(quote! {
+                if let Some(group) = self.group() {
+                    defmt::write!(f, "{=str}: ", group);
+                }
+            });
+        }
+
+        quote! {
+            impl #impl_generics defmt::Format for #enum_ty #where_clause {
+                fn format(&self, f: defmt::Formatter<'_>) {
+                    #defmt_prefix
+                    #[allow(unreachable_patterns, unused_variables)]
+                    match self {
+                        #defmt_list
+                        _ => defmt::write!(f, "{=str}", #variant_name),
+                    }
+                }
+            }
+        }
+    }
+
+    fn impl_log(&self) -> TokenStream {
+        if ! self.log {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let vis = &self.vis;
+        let (_, enum_ty, _) = self.split_generics();
+        let ext_id = Ident::new(&format!("{}ResultExt", enum_id), Span::call_site());
+        let doc = format!("Logs `{}` error with chain and returns result unchanged", enum_id);
+
+        let mut generics = self.generics.clone();
+        generics.params.push(syn::parse_quote! { __T });
+        let (impl_generics, _, _) = generics.split_for_impl();
+        let log_where = self.where_with(quote! {
+            #enum_ty: ::std::error::Error + 'static,
+        });
+
+        quote! {
+            #[doc = #doc]
+            #vis trait #ext_id: Sized {
+                /// Logs error with the given level
+                fn log_err(self, level: log::Level) -> Self;
+
+                /// Logs error with the `Warn` level
+                #[inline]
+                fn log_warn(self) -> Self {
+                    self.log_err(log::Level::Warn)
+                }
+            }
+
+            impl #impl_generics #ext_id for ::core::result::Result<__T, #enum_ty> #log_where {
+                fn log_err(self, level: log::Level) -> Self {
+                    if let Err(e) = &self {
+                        let mut text = e.to_string();
+                        let mut source = ::std::error::Error::source(e);
+                        while let Some(e) = source {
+                            text.push_str(": ");
+                            text.push_str(&e.to_string());
+                            source = e.source();
+                        }
+                        log::log!(level, "{}", text);
+                    }
+                    self
+                }
+            }
+        }
+    }
+
+    fn impl_syslog(&self) -> TokenStream {
+        if ! self.syslog {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let severity_id = Ident::new(&format!("{}Severity", self.enum_id), Span::call_site());
+        let syslog_where = self.where_with(quote! {
+            #enum_ty: ::std::error::Error + 'static,
+        });
+        let variant_name = self.variant_name_expr(quote! { self });
+
+        quote! {
+            impl #severity_id {
+                /// Returns syslog priority of the level: `debug` for trace and debug,
+                /// `warning` for warn, `err` for error, and `crit` for fatal
+                pub fn syslog_priority(&self) -> u8 {
+                    match self {
+                        #severity_id::Trace | #severity_id::Debug => 7,
+                        #severity_id::Warn => 4,
+                        #severity_id::Error => 3,
+                        #severity_id::Fatal => 2,
+                    }
+                }
+            }
+
+            impl #impl_generics #enum_ty #syslog_where {
+                /// Returns error message with messages of the source errors
+                fn syslog_text(&self) -> String {
+                    let mut text = self.to_string();
+                    let mut source = ::std::error::Error::source(self);
+                    while let Some(e) = source {
+                        text.push_str(": ");
+                        text.push_str(&e.to_string());
+                        source = e.source();
+                    }
+                    text
+                }
+
+                /// Returns syslog message `<PRI>text` with priority of the error severity
+                /// and given facility, e.g. `3` for daemon or `16` for local0
+                pub fn syslog_message(&self, facility: u8) -> String {
+                    let pri = u16::from(facility) * 8 + u16::from(self.severity().syslog_priority());
+                    format!("<{}>{}", pri, self.syslog_text())
+                }
+
+                /// Returns journald fields: `PRIORITY`, `MESSAGE` with chain,
+                /// `ERROR_VARIANT`, and `ERROR_SOURCE` for each source error
+                pub fn journal_fields(&self) -> Vec<(&'static str, String)> {
+                    let mut fields = vec![
+                        ("PRIORITY", self.severity().syslog_priority().to_string()),
+                        ("MESSAGE", self.syslog_text()),
+                        ("ERROR_VARIANT", #variant_name.to_owned()),
+                    ];
+                    let mut source = ::std::error::Error::source(self);
+                    while let Some(e) = source {
+                        fields.push(("ERROR_SOURCE", e.to_string()));
+                        source = e.source();
+                    }
+                    fields
+                }
+
+                /// Sends `syslog_message()` to the `/dev/log`
+                #[cfg(unix)]
+                pub fn emit_syslog(&self, facility: u8) -> ::std::io::Result<()> {
+                    let socket = ::std::os::unix::net::UnixDatagram::unbound()?;
+                    socket.send_to(self.syslog_message(facility).as_bytes(), "/dev/log")?;
+                    Ok(())
+                }
+
+                /// Sends `journal_fields()` to the journald with native protocol
+                #[cfg(unix)]
+                pub fn emit_journal(&self) -> ::std::io::Result<()> {
+                    let mut payload = Vec::new();
+                    for (key, value) in self.journal_fields() {
+                        payload.extend_from_slice(key.as_bytes());
+                        if value.contains('\n') {
+                            payload.push(b'\n');
+                            payload.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                        } else {
+                            payload.push(b'=');
+                        }
+                        payload.extend_from_slice(value.as_bytes());
+                        payload.push(b'\n');
+                    }
+                    let socket = ::std::os::unix::net::UnixDatagram::unbound()?;
+                    socket.send_to(&payload, "/run/systemd/journal/socket")?;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    fn impl_diagnostic(&self) -> TokenStream {
+        if ! self.diagnostic {
+            return TokenStream::new()
+        }
+
+        let (impl_generics, enum_ty, _) = self.split_generics();
+        let diagnostic_where = self.where_with(quote! {
+            #enum_ty: ::std::error::Error,
+        });
+
+        let mut method_list = TokenStream::new();
+        if ! self.code_table.is_empty() {
+            method_list.extend(quote! {
+                fn code<'a>(&'a self) -> Option<Box<dyn ::core::fmt::Display + 'a>> {
+                    Some(Box::new(Self::code(self)))
+                }
+            });
+        }
+        if ! self.help_list.is_empty() {
+            method_list.extend(quote! {
+                fn help<'a>(&'a self) -> Option<Box<dyn ::core::fmt::Display + 'a>> {
+                    Self::help(self).map(|v| Box::new(v) as Box<dyn ::core::fmt::Display>)
+                }
+            });
+        }
+        if ! self.url_list.is_empty() {
+            method_list.extend(quote! {
+                fn url<'a>(&'a self) -> Option<Box<dyn ::core::fmt::Display + 'a>> {
+                    Self::url(self).map(|v| Box::new(v) as Box<dyn ::core::fmt::Display>)
+                }
+            });
+        }
+
+        quote! {
+            impl #impl_generics miette::Diagnostic for #enum_ty #diagnostic_where {
+                #method_list
+            }
+        }
+    }
+
+    fn impl_io_class(&self) -> TokenStream {
+        if ! self.io_class {
+            return TokenStream::new()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let vis = &self.vis;
+        let class_id = Ident::new(&format!("{}IoClass", enum_id), Span::call_site());
+
+        quote! {
+            /// Class of the `std::io::Error` in the error chain
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            #vis enum #class_id {
+                NotFound,
+                PermissionDenied,
+                AlreadyExists,
+                Timeout,
+                ConnectionRefused,
+                ConnectionReset,
+                Interrupted,
+                InvalidInput,
+                InvalidData,
+                UnexpectedEof,
+                Other,
+            }
+
+            impl #impl_generics #enum_ty #where_clause {
+                /// Returns class of the first `std::io::Error` in the error chain
+                pub fn io_class(&self) -> Option<#class_id>
+                where
+                    Self: ::std::error::Error + 'static,
+                {
+                    let mut source: Option<&(dyn ::std::error::Error + 'static)> = Some(self);
+                    while let Some(e) = source {
+                        if let Some(e) = e.downcast_ref::<::std::io::Error>() {
+                            return Some(match e.kind() {
+                                ::std::io::ErrorKind::NotFound => #class_id::NotFound,
+                                ::std::io::ErrorKind::PermissionDenied => #class_id::PermissionDenied,
+                                ::std::io::ErrorKind::AlreadyExists => #class_id::AlreadyExists,
+                                ::std::io::ErrorKind::TimedOut |
+                                ::std::io::ErrorKind::WouldBlock => #class_id::Timeout,
+                                ::std::io::ErrorKind::ConnectionRefused => #class_id::ConnectionRefused,
+                                ::std::io::ErrorKind::ConnectionReset |
+                                ::std::io::ErrorKind::ConnectionAborted |
+                                ::std::io::ErrorKind::NotConnected |
+                                ::std::io::ErrorKind::BrokenPipe => #class_id::ConnectionReset,
+                                ::std::io::ErrorKind::Interrupted => #class_id::Interrupted,
+                                ::std::io::ErrorKind::InvalidInput => #class_id::InvalidInput,
+                                ::std::io::ErrorKind::InvalidData => #class_id::InvalidData,
+                                ::std::io::ErrorKind::UnexpectedEof => #class_id::UnexpectedEof,
+                                _ => #class_id::Other,
+                            })
+                        }
+                        source = e.source();
+                    }
+                    None
+                }
+            }
+        }
+    }
+
+    fn build(&mut self, data: &syn::DataEnum) -> TokenStream {
+        if self.delegate && data.variants.len() != 1 {
+            let message = "error_delegate required enum with single variant";
+            self.error_list.push(syn::Error::new_spanned(&self.enum_id, message));
+            return self.build_impl()
+        }
+
+        self.primary_list = data.variants.iter().filter_map(primary_type).collect();
+        self.single_variant = data.variants.len() == 1;
+
+        for variant in &data.variants {
+            self.impl_variant(variant);
+        }
+
+        self.build_impl()
+    }
+
+    /// Struct implemented as enum with single variant
+    /// which attributes defined on the struct
+    fn build_struct(&mut self, attrs: &[syn::Attribute], data: &syn::DataStruct) -> TokenStream {
+        if let Some(remote) = &self.remote {
+            let message = "error_remote required enum";
+            self.error_list.push(syn::Error::new_spanned(remote, message));
+            return self.build_impl()
+        }
+
+        self.is_struct = true;
+
+        let variant = syn::Variant {
+            attrs: attrs.iter()
+                .filter(|v| ! v.path.is_ident("error_truncate"))
+                .cloned()
+                .collect(),
+            ident: self.enum_id.clone(),
+            fields: data.fields.clone(),
+            discriminant: None,
+        };
+        self.impl_variant(&variant);
+
+        self.build_impl()
+    }
+
+    fn build_impl(&mut self) -> TokenStream {
+        let code_impl = self.impl_code();
+        let severity_impl = self.impl_severity();
+        let category_impl = self.impl_category();
+        let exit_code_impl = self.impl_exit_code();
+        let ffi_impl = self.impl_ffi();
+        let status_impl = self.impl_status();
+
+        if ! self.error_list.is_empty() {
+            return self.error_list.iter().map(syn::Error::to_compile_error).collect()
+        }
+
+        let enum_id = &self.enum_id;
+        let (impl_generics, enum_ty, where_clause) = self.split_generics();
+        let display_list = &self.display_list;
+        let source_list = &self.source_list;
+        let from_list = &self.from_list;
+        let error_trait = self.error_trait();
+        let defmt_impl = self.impl_defmt();
+        let kind_enum = self.impl_kind_enum();
+        let result_alias = self.impl_result_alias();
+
+        let mut audit_impl = TokenStream::new();
+        if self.audit {
+            let audit_list = &self.audit_list;
+            audit_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// List of the generated `From` conversions:
+                    /// source type and variant name
+                    #[doc(hidden)]
+                    pub const ERROR_CONVERSIONS: &'static [(&'static str, &'static str)] = &[
+                        #audit_list
+                    ];
+                }
+            });
+        }
+
+        let mut remote_impl = TokenStream::new();
+        if let Some(remote) = &self.remote {
+            let remote_list = &self.remote_list;
+            remote_impl.extend(quote! {
+                impl #impl_generics From<#remote> for #enum_ty #where_clause {
+                    fn from(error: #remote) -> Self {
+                        match error {
+                            #remote_list
+                        }
+                    }
+                }
+            });
+        }
+        let enum_name = enum_id.to_string();
+        let variant_name = self.variant_name_expr(quote! { self });
+
+        let mut source_adapter = TokenStream::new();
+        if self.source_adapter {
+            source_adapter.extend(quote! {
+                struct __Source<'a, T: ?Sized>(&'a T);
+
+                trait __ViaError<'a> {
+                    fn __source(&self) -> &'a (dyn #error_trait + 'static);
+                }
+
+                impl<'a, T: #error_trait + 'static> __ViaError<'a> for &__Source<'a, T> {
+                    fn __source(&self) -> &'a (dyn #error_trait + 'static) { self.0 }
+                }
+
+                #[allow(dead_code)]
+                trait __ViaDeref<'a> {
+                    fn __source(&self) -> &'a (dyn #error_trait + 'static);
+                }
+
+                impl<'a, T> __ViaDeref<'a> for __Source<'a, T>
+                where
+                    T: ::core::ops::Deref<Target = dyn #error_trait + Send + Sync + 'static>,
+                {
+                    fn __source(&self) -> &'a (dyn #error_trait + 'static) { &**self.0 }
+                }
+            });
+        }
+
+        let mut std_impl = TokenStream::new();
+        if ! self.no_std {
+            let io_kind_list = &self.io_kind_list;
+            let mut into_io = TokenStream::new();
+            if ! self.into_io_list.is_empty() {
+                let into_io_list = &self.into_io_list;
+                into_io.extend(quote! {
+                    #[allow(unreachable_patterns)]
+                    let error = match error {
+                        #into_io_list
+                        error => error,
+                    };
+                });
+            }
+            let io_where = self.where_with(quote! {
+                #enum_ty: ::std::error::Error + Send + Sync + 'static,
+            });
+
+            std_impl.extend(quote! {
+                impl #impl_generics From<#enum_ty> for ::std::io::Error #io_where {
+                    fn from(error: #enum_ty) -> Self {
+                        #source_adapter
+                        #into_io
+                        #[allow(unreachable_patterns)]
+                        let kind = match &error {
+                            #io_kind_list
+                            _ => ::std::io::ErrorKind::Other,
+                        };
+                        Self::new(kind, error)
+                    }
+                }
+            });
+        }
+
+        if self.fingerprint && ! self.no_std {
+            std_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns stable hash of the error type, variant name and
+                    /// kind of the innermost `std::io::Error` in the chain.
+                    /// Payloads are not included.
+                    pub fn fingerprint(&self) -> u64
+                    where
+                        Self: ::std::error::Error + 'static,
+                    {
+                        let variant = #variant_name;
+
+                        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+                        let mut update = |data: &str| {
+                            for b in data.bytes() {
+                                hash ^= u64::from(b);
+                                hash = hash.wrapping_mul(0x0100_0000_01b3);
+                            }
+                        };
+
+                        update(#enum_name);
+                        update("::");
+                        update(variant);
+
+                        let mut root: &(dyn ::std::error::Error + 'static) = self;
+                        while let Some(e) = root.source() {
+                            root = e;
+                        }
+                        if let Some(e) = root.downcast_ref::<::std::io::Error>() {
+                            update(&format!("{:?}", e.kind()));
+                        }
+
+                        hash
+                    }
+                }
+            });
+        }
+
+        if ! self.no_std {
+            std_impl.extend(self.impl_crash_report());
+            std_impl.extend(self.impl_serialize());
+            std_impl.extend(self.impl_diagnostic());
+            std_impl.extend(self.impl_log());
+            std_impl.extend(self.impl_syslog());
+            std_impl.extend(self.impl_error_or_kind_ext());
+            std_impl.extend(self.impl_error_sink_ext());
+            std_impl.extend(self.impl_wasm());
+            std_impl.extend(self.impl_axum());
+            std_impl.extend(self.impl_tonic());
+            std_impl.extend(self.impl_pyo3());
+            std_impl.extend(self.impl_error_stack());
+            std_impl.extend(self.impl_io_class());
+        }
+
+        let mut error_where = where_clause.clone();
+        if ! self.generics.params.is_empty() {
+            let source_type_list = &self.source_type_list;
+            error_where = self.where_with(quote! {
+                #enum_ty: ::core::fmt::Debug,
+                #( #source_type_list: 'static, )*
+            });
+        }
+
+        let mut display_where = where_clause.clone();
+        if ! self.generics.params.is_empty() {
+            let source_type_list = &self.source_type_list;
+            display_where = self.where_with(quote! {
+                #( #source_type_list: 'static, )*
+            });
+        }
+
+        let mut interrupt_impl = TokenStream::new();
+        if ! self.timeout_list.is_empty() || ! self.cancelled_list.is_empty() {
+            let timeout_list = &self.timeout_list;
+            let cancelled_list = &self.cancelled_list;
+            interrupt_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns `true` if variant defined with `#[error_timeout]`
+                    pub fn is_timeout(&self) -> bool {
+                        match self {
+                            #timeout_list
+                            _ => false,
+                        }
+                    }
+
+                    /// Returns `true` if variant defined with `#[error_cancelled]`
+                    pub fn is_cancelled(&self) -> bool {
+                        match self {
+                            #cancelled_list
+                            _ => false,
+                        }
+                    }
+                }
+            });
+        }
+
+        let deadline_impl = self.impl_error_deadline_ext();
+
+        let mut transient_impl = TokenStream::new();
+        if ! self.transient_list.is_empty() {
+            let transient_list = &self.transient_list;
+            transient_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns `true` if variant defined with `#[error_transient]`
+                    /// or nested error defined with `#[error_transient(source)]` is transient
+                    pub fn is_transient(&self) -> bool {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #transient_list
+                            _ => false,
+                        }
+                    }
+                }
+            });
+        }
+
+        let mut examples_impl = TokenStream::new();
+        if self.examples {
+            let example_list = &self.example_list;
+            examples_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns unit variants and variants defined with `#[error_example]`
+                    /// with default values of the fields
+                    pub fn examples() -> Vec<Self> {
+                        #[allow(unused_mut)]
+                        let mut list = Vec::new();
+                        #example_list
+                        list
+                    }
+                }
+            });
+        }
+
+        let mut snapshot_impl = TokenStream::new();
+        if self.snapshot {
+            snapshot_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns multiline text with type, variant, message
+                    /// and chain of the error for snapshot tests
+                    pub fn snapshot(&self) -> String
+                where
+                    Self: ::std::error::Error,
+                {
+                        let variant = #variant_name;
+
+                        let mut text = String::new();
+                        text.push_str(&format!("type: {}\n", #enum_name));
+                        text.push_str(&format!("variant: {}\n", variant));
+                        text.push_str(&format!("message: {}\n", self));
+
+                        let mut source = ::std::error::Error::source(self);
+                        while let Some(e) = source {
+                            text.push_str(&format!("caused by: {}\n", e));
+                            source = e.source();
+                        }
+
+                        text
+                    }
+
+                    /// Compares messages of the error chain with the expected list.
+                    /// Returns `None` if chains are equal, or report with
+                    /// mismatching, missing and extra levels
+                    pub fn chain_diff(&self, expected: &[&str]) -> Option<String>
+                    where
+                        Self: ::std::error::Error,
+                    {
+                        let mut actual = vec![self.to_string()];
+                        let mut source = ::std::error::Error::source(self);
+                        while let Some(e) = source {
+                            actual.push(e.to_string());
+                            source = e.source();
+                        }
+
+                        let mut text = String::new();
+                        for i in 0 .. ::core::cmp::max(actual.len(), expected.len()) {
+                            match (expected.get(i), actual.get(i)) {
+                                (Some(e), Some(a)) if *e == a.as_str() => {}
+                                (Some(e), Some(a)) => text.push_str(&format!("level {}: expected {:?}, found {:?}\n", i, e, a)),
+                                (Some(e), None) => text.push_str(&format!("level {}: missing {:?}\n", i, e)),
+                                (None, Some(a)) => text.push_str(&format!("level {}: extra {:?}\n", i, a)),
+                                (None, None) => {}
+                            }
+                        }
+
+                        if text.is_empty() {
+                            None
+                        } else {
+                            Some(text)
+                        }
+                    }
+                }
+            });
+        }
+
+        let mut eq_kind_impl = TokenStream::new();
+        if self.eq_kind {
+            let eq_kind_list = &self.eq_kind_list;
+            eq_kind_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Compares variants and payload fields, wrapped source errors are ignored
+                    pub fn eq_kind(&self, other: &Self) -> bool {
+                        match (self, other) {
+                            #eq_kind_list
+                            _ => false,
+                        }
+                    }
+                }
+            });
+        }
+        if self.eq {
+            eq_kind_impl.extend(quote! {
+                impl #impl_generics PartialEq for #enum_ty #where_clause {
+                    #[inline]
+                    fn eq(&self, other: &Self) -> bool {
+                        self.eq_kind(other)
+                    }
+                }
+            });
+        }
+        if self.eq_total {
+            let eq_type_list = &self.eq_type_list;
+            let eq_where = self.where_with(quote! {
+                #( #eq_type_list: Eq, )*
+            });
+            eq_kind_impl.extend(quote! {
+                impl #impl_generics Eq for #enum_ty #eq_where {}
+            });
+        }
+
+        let mut builder_list = self.builder_list.clone();
+        if ! builder_list.is_empty() {
+            let vis = &self.vis;
+            let missing_id = Ident::new(&format!("{}MissingField", self.enum_id), Span::call_site());
+            builder_list.extend(quote! {
+                /// Required field which is not defined in the builder
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                #vis struct #missing_id {
+                    /// Name of the builder
+                    pub builder: &'static str,
+                    /// Name of the field
+                    pub field: &'static str,
+                }
+
+                impl ::core::fmt::Display for #missing_id {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        write!(f, "{}: field {} required", self.builder, self.field)
+                    }
+                }
+
+                impl #error_trait for #missing_id {}
+            });
+        }
+        let delegate_impl = &self.delegate_impl;
+
+        let mut constructor_impl = TokenStream::new();
+        if self.constructors {
+            let constructor_list = &self.constructor_list;
+            constructor_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    #constructor_list
+                }
+            });
+        }
+
+        let mut accessor_impl = TokenStream::new();
+        if self.accessors {
+            let accessor_list = &self.accessor_list;
+            accessor_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    #accessor_list
+                }
+            });
+        }
+
+        let mut location_impl = TokenStream::new();
+        let mut display_location = TokenStream::new();
+        if ! self.location_list.is_empty() {
+            let location_list = &self.location_list;
+            location_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns location where error was created
+                    pub fn location(&self) -> Option<&'static ::core::panic::Location<'static>> {
+                        match self {
+                            #location_list
+                            _ => None,
+                        }
+                    }
+                }
+            });
+            display_location.extend(quote! {
+                if let Some(location) = self.location() {
+                    write!(f, "\n    at {}", location)?;
+                }
+            });
+        }
+
+        let mut backtrace_impl = TokenStream::new();
+        if ! self.backtrace_list.is_empty() {
+            let backtrace_list = &self.backtrace_list;
+            backtrace_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns backtrace captured on the error creation
+                    pub fn backtrace(&self) -> Option<&::std::backtrace::Backtrace> {
+                        match self {
+                            #backtrace_list
+                            _ => None,
+                        }
+                    }
+                }
+            });
+        }
+
+        let mut help_impl = TokenStream::new();
+        let mut display_help = TokenStream::new();
+        if ! self.help_list.is_empty() {
+            let help_list = &self.help_list;
+            help_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns help text defined with `#[error_help]`
+                    pub fn help(&self) -> Option<&'static str> {
+                        match self {
+                            #help_list
+                            _ => None,
+                        }
+                    }
+                }
+            });
+            display_help.extend(quote! {
+                if let Some(help) = self.help() {
+                    write!(f, "\n    Help: {}", help)?;
+                }
+            });
+        }
+
+        let mut url_impl = TokenStream::new();
+        if ! self.url_list.is_empty() {
+            let url_list = &self.url_list;
+            url_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns documentation link defined with `#[error_url]`
+                    pub fn url(&self) -> Option<&'static str> {
+                        #[allow(unreachable_patterns)]
+                        match self {
+                            #url_list
+                            _ => None,
+                        }
+                    }
+                }
+            });
+            display_help.extend(quote! {
+                if let Some(url) = self.url() {
+                    write!(f, "\n    See: {}", url)?;
+                }
+            });
+        }
+
+        let mut map_impl = TokenStream::new();
+        if self.map {
+            let map_list = &self.map_list;
+            map_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    #map_list
+                }
+            });
+        }
+
+        let mut display_adapter = TokenStream::new();
+        if self.lazy {
+            display_adapter.extend(quote! {
+                struct LazyDisplay<'a>(&'a dyn Fn(&mut ::core::fmt::Formatter) -> ::core::fmt::Result);
+
+                impl ::core::fmt::Display for LazyDisplay<'_> {
+                    #[inline]
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        (self.0)(f)
+                    }
+                }
+            });
+        }
+
+        if self.hex {
+            display_adapter.extend(quote! {
+                struct DisplayHex<'a>(&'a [u8], usize);
+
+                impl ::core::fmt::Display for DisplayHex<'_> {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        for b in self.0.iter().take(self.1) {
+                            write!(f, "{:02x}", b)?;
+                        }
+                        if self.0.len() > self.1 {
+                            write!(f, "... ({} bytes)", self.0.len())?;
+                        }
+                        Ok(())
+                    }
+                }
+            });
+        }
+
+        if self.path_adapter {
+            display_adapter.extend(quote! {
+                struct __PathDisplay<'a, T: ?Sized>(&'a T);
+
+                trait __ViaPath<'a> {
+                    fn __display(&self) -> ::std::path::Display<'a>;
+                }
+
+                impl<'a> __ViaPath<'a> for &__PathDisplay<'a, ::std::path::Path> {
+                    fn __display(&self) -> ::std::path::Display<'a> { self.0.display() }
+                }
+
+                impl<'a> __ViaPath<'a> for &__PathDisplay<'a, ::std::path::PathBuf> {
+                    fn __display(&self) -> ::std::path::Display<'a> { self.0.display() }
+                }
+
+                #[allow(dead_code)]
+                trait __ViaDisplay<'a, T: ?Sized> {
+                    fn __display(&self) -> &'a T;
+                }
+
+                impl<'a, T: ?Sized + ::core::fmt::Display> __ViaDisplay<'a, T> for __PathDisplay<'a, T> {
+                    fn __display(&self) -> &'a T { self.0 }
+                }
+            });
+        }
+
+        if self.truncate_field {
+            display_adapter.extend(quote! {
+                struct DisplayTruncate<'a>(&'a dyn ::core::fmt::Display, usize);
+
+                impl ::core::fmt::Display for DisplayTruncate<'_> {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        struct Limit<'a, 'b> {
+                            f: &'a mut ::core::fmt::Formatter<'b>,
+                            left: usize,
+                            cut: bool,
+                        }
+
+                        impl ::core::fmt::Write for Limit<'_, '_> {
+                            fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+                                if self.cut {
+                                    return Ok(())
+                                }
+                                match s.char_indices().nth(self.left) {
+                                    Some((end, _)) => {
+                                        self.cut = true;
+                                        self.f.write_str(&s[.. end])
+                                    }
+                                    None => {
+                                        self.left -= s.chars().count();
+                                        self.f.write_str(s)
+                                    }
+                                }
+                            }
+                        }
+
+                        let mut w = Limit { f, left: self.1, cut: false };
+                        ::core::fmt::Write::write_fmt(&mut w, format_args!("{}", self.0))?;
+                        if w.cut {
+                            w.f.write_str("...")?;
+                        }
+                        Ok(())
+                    }
+                }
+            });
+        }
+
+        let mut display_message = quote! {
+            error_rules_message(f)?;
+        };
+        if self.truncate.is_some() || ! self.truncate_list.is_empty() {
+            let truncate_list = &self.truncate_list;
+            let truncate = match self.truncate {
+                Some(v) => quote! { Some(#v) },
+                None => quote! { None },
+            };
+            display_message = quote! {
+                let limit: Option<usize> = match self {
+                    #truncate_list
+                    _ => #truncate,
+                };
+                if let Some(limit) = limit {
+                    let mut text = String::new();
+                    error_rules_message(&mut text)?;
+                    match text.char_indices().nth(limit) {
+                        Some((end, _)) => write!(f, "{}...", &text[.. end])?,
+                        None => f.write_str(&text)?,
+                    }
+                } else {
+                    error_rules_message(f)?;
+                }
+            };
+        }
+
+        let mut display_prefix = TokenStream::new();
+        if self.prefix_module || ! self.prefix.is_empty() {
+            let prefix = self.prefix_expr();
+            display_prefix.extend(quote! {
+                write!(f, "{}: ", #prefix)?;
+            });
+        }
+
+        if ! self.format_prefix_list.is_empty() && ! display_prefix.is_empty() {
+            let format_prefix_list = &self.format_prefix_list;
+            display_prefix = quote! {
+                let format_prefix = match self {
+                    #format_prefix_list
+                    _ => false,
+                };
+                if ! format_prefix {
+                    #display_prefix
+                }
+            };
+        }
+
+        let mut group_impl = TokenStream::new();
+        if ! self.group_list.is_empty() {
+            let group_list = &self.group_list;
+            group_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns group name of the variant defined with `#[error_group]`
+                    pub fn group(&self) -> Option<&'static str> {
+                        match self {
+                            #group_list
+                            _ => None,
+                        }
+                    }
+                }
+            });
+            display_prefix.extend(quote! {
+                if let Some(group) = self.group() {
+                    write!(f, "{}: ", group)?;
+                }
+            });
+        }
 
-extern crate proc_macro;
-
-use proc_macro2::{TokenStream, Span, Ident};
-use quote::quote;
-use syn::{
-    self,
-    parse_macro_input,
-};
-
-
-fn impl_display_item(meta_list: &syn::MetaList) -> TokenStream {
-    let mut attr_list = TokenStream::new();
+        if ! self.transparent_list.is_empty() && ! display_prefix.is_empty() {
+            let transparent_list = &self.transparent_list;
+            display_prefix = quote! {
+                let transparent = match self {
+                    #transparent_list
+                    _ => false,
+                };
+                if ! transparent {
+                    #display_prefix
+                }
+            };
+        }
 
-    let fmt = match &meta_list.nested[0] {
-        syn::NestedMeta::Lit(syn::Lit::Str(v)) => v.value(),
-        _ => panic!("first attribute shoud be literal"),
-    };
-    attr_list.extend(quote! { #fmt });
+        let mut chain_impl = TokenStream::new();
+        if self.chain {
+            chain_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns iterator over the error and its sources
+                    pub fn iter_chain(&self) -> impl Iterator<Item = &(dyn #error_trait + 'static)>
+                    where
+                        Self: #error_trait + 'static,
+                    {
+                        let error: &(dyn #error_trait + 'static) = self;
+                        ::core::iter::successors(Some(error), |e| #error_trait::source(*e))
+                    }
 
-    for attr in meta_list.nested.iter().skip(1) {
-        let attr = match attr {
-            syn::NestedMeta::Lit(syn::Lit::Int(v)) => v.base10_parse::<u32>().unwrap(),
-            _ => panic!("attributes should be number"),
-        };
+                    /// Returns the innermost source of the error or error itself
+                    pub fn root_cause(&self) -> &(dyn #error_trait + 'static)
+                    where
+                        Self: #error_trait + 'static,
+                    {
+                        self.iter_chain().last().unwrap()
+                    }
 
-        let attr_id = Ident::new(&format!("i{}", attr), Span::call_site());
-        attr_list.extend(quote! { , #attr_id });
-    }
+                    /// Returns wrapper to display error and its sources separated with `: `
+                    pub fn display_chain(&self) -> impl ::core::fmt::Display + '_
+                    where
+                        Self: #error_trait + 'static,
+                    {
+                        struct DisplayChain<'a>(&'a (dyn #error_trait + 'static));
 
-    attr_list
-}
+                        impl ::core::fmt::Display for DisplayChain<'_> {
+                            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                                write!(f, "{}", self.0)?;
+                                let mut source = self.0.source();
+                                while let Some(e) = source {
+                                    write!(f, ": {}", e)?;
+                                    source = e.source();
+                                }
+                                Ok(())
+                            }
+                        }
 
+                        DisplayChain(self)
+                    }
+                }
+            });
+        }
 
-struct ErrorRules {
-    enum_id: Ident,
-    prefix: String,
-    from_list: TokenStream,
-    source_list: TokenStream,
-    display_list: TokenStream,
-}
+        let mut variant_name_impl = TokenStream::new();
+        if self.variant_name {
+            variant_name_impl.extend(quote! {
+                impl #impl_generics #enum_ty #where_clause {
+                    /// Returns name of the variant
+                    pub fn variant_name(&self) -> &'static str {
+                        #variant_name
+                    }
+                }
+            });
+        }
 
+        quote! {
+            impl #impl_generics ::core::fmt::Display for #enum_ty #display_where {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    let error_rules_message = |f: &mut dyn ::core::fmt::Write| -> ::core::fmt::Result {
+                        #display_adapter
+                        #display_prefix
+                        match self {
+                            #display_list
+                        }
+                    };
 
-impl ErrorRules {
-    fn new(ident: &Ident) -> ErrorRules {
-        ErrorRules {
-            enum_id: ident.clone(),
-            prefix: String::default(),
-            from_list: TokenStream::default(),
-            source_list: TokenStream::default(),
-            display_list: TokenStream::default(),
-        }
-    }
+                    #display_message
 
-    fn impl_error_from_fields(&mut self,
-        item_id: &TokenStream,
-        variant: &syn::Variant)
-    {
-        let enum_id = &self.enum_id;
+                    if f.alternate() {
+                        #display_location
+                        #source_adapter
+                        let mut source: Option<&(dyn #error_trait + 'static)> = match self {
+                            #source_list
+                            _ => None,
+                        };
+                        while let Some(e) = source {
+                            write!(f, "\n    Caused by: {}", e)?;
+                            source = e.source();
+                        }
+                        #display_help
+                    }
 
-        match &variant.fields {
-            syn::Fields::Unnamed(fields) => {
-                if fields.unnamed.len() != 1 {
-                    panic!("variant should contain one field")
+                    Ok(())
                 }
-                let field = &fields.unnamed[0];
-                let ty = &field.ty;
-                self.from_list.extend(quote! {
-                    impl From<#ty> for #enum_id {
-                        #[inline]
-                        fn from(e: #ty) -> #enum_id { #item_id ( e ) }
+            }
+
+            impl #impl_generics #error_trait for #enum_ty #error_where {
+                fn source(&self) -> Option<&(dyn #error_trait + 'static)> {
+                    #source_adapter
+                    match self {
+                        #source_list
+                        _ => None,
                     }
-                });
-                self.source_list.extend(quote! {
-                    #item_id (i0) => Some(i0),
-                });
+                }
             }
-            _ => panic!("field format mismatch"),
-        };
-    }
 
-    fn impl_error_from_path(&mut self,
-        item_id: &TokenStream,
-        variant: &syn::Variant)
-    {
-        self.impl_error_from_fields(&item_id, variant);
+            #variant_name_impl
 
-        self.display_list.extend(quote! {
-            #item_id ( i0 ) => write!(f, "{}", i0),
-        });
-    }
+            #chain_impl
 
-    fn impl_error_from_list(&mut self,
-        item_id: &TokenStream,
-        variant: &syn::Variant,
-        meta_list: &syn::MetaList)
-    {
-        if meta_list.nested.is_empty() {
-            self.impl_error_from_path(item_id, variant);
-            return
-        }
+            #remote_impl
 
-        self.impl_error_from_fields(item_id, variant);
+            #std_impl
 
-        let w = impl_display_item(meta_list);
-        self.display_list.extend(quote! {
-            #item_id ( i0 ) => write!(f, #w),
-        });
-    }
+            #defmt_impl
 
-    fn impl_error_from(&mut self,
-        item_id: &TokenStream,
-        variant: &syn::Variant,
-        meta: &syn::Meta)
-    {
-        match meta {
-            syn::Meta::Path(_) => self.impl_error_from_path(item_id, variant),
-            syn::Meta::List(v) => self.impl_error_from_list(item_id, variant, v),
-            _ => panic!("meta format mismatch"),
-        }
-    }
+            #kind_enum
 
-    fn impl_error_kind_list(&mut self,
-        item_id: &TokenStream,
-        variant: &syn::Variant,
-        meta_list: &syn::MetaList)
-    {
-        if meta_list.nested.is_empty() {
-            panic!("meta format mismatch")
-        }
+            #result_alias
 
-        match &variant.fields {
-            syn::Fields::Unit => {
-                let w = impl_display_item(meta_list);
-                self.display_list.extend(quote! {
-                    #item_id => write!(f, #w),
-                });
-            }
-            syn::Fields::Unnamed(fields) => {
-                let mut ident_list = TokenStream::new();
-                for i in 0 .. fields.unnamed.len() {
-                    let field_id = Ident::new(&format!("i{}", i), Span::call_site());
-                    ident_list.extend(quote! { #field_id, });
-                }
+            #code_impl
 
-                let w = impl_display_item(meta_list);
-                self.display_list.extend(quote! {
-                    #item_id ( #ident_list ) => write!(f, #w),
-                });
-            }
-            _ => panic!("field format mismatch"),
-        };
+            #audit_impl
+
+            #group_impl
+
+            #eq_kind_impl
+
+            #snapshot_impl
+
+            #examples_impl
+
+            #interrupt_impl
+
+            #deadline_impl
+
+            #map_impl
+
+            #help_impl
+
+            #url_impl
+
+            #backtrace_impl
+
+            #location_impl
+
+            #severity_impl
+
+            #exit_code_impl
+
+            #ffi_impl
+
+            #status_impl
+
+            #transient_impl
+
+            #category_impl
+
+            #constructor_impl
+
+            #accessor_impl
+
+            #builder_list
+
+            #delegate_impl
+
+            #from_list
+        }
     }
 
-    fn impl_error_kind(&mut self,
-        item_id: &TokenStream,
-        variant: &syn::Variant,
-        meta: &syn::Meta)
-    {
+    fn set_prefix(&mut self, meta: &syn::Meta) {
         match meta {
-            syn::Meta::List(v) => self.impl_error_kind_list(item_id, variant, v),
-            _ => panic!("meta format mismatch"),
+            syn::Meta::NameValue(syn::MetaNameValue { lit: syn::Lit::Str(v), .. }) => {
+                self.prefix = v.value();
+            }
+            syn::Meta::List(v) if v.nested.len() == 1 => {
+                let mode = match &v.nested[0] {
+                    syn::NestedMeta::Meta(syn::Meta::Path(v)) => v.get_ident().map(Ident::to_string),
+                    _ => None,
+                };
+                let name = self.enum_id.to_string();
+                match mode.as_deref() {
+                    Some("auto") => match name.strip_suffix("Error") {
+                        Some(v) if ! v.is_empty() => self.prefix = v.to_owned(),
+                        _ => self.prefix_module = true,
+                    },
+                    Some("module") => self.prefix_module = true,
+                    _ => {
+                        let message = "error_prefix should be auto or module";
+                        self.error_list.push(syn::Error::new_spanned(v, message));
+                    }
+                }
+            }
+            _ => self.meta_mismatch(meta),
         }
     }
 
-    fn impl_variant(&mut self, variant: &syn::Variant) {
-        let enum_id = &self.enum_id;
-        let item_id = &variant.ident;
-        let item_id = quote! { #enum_id::#item_id };
+    fn set_delegate(&mut self, meta: &syn::Meta) {
+        self.delegate = true;
+        let list = match meta {
+            syn::Meta::Path(_) => return,
+            syn::Meta::List(v) => &v.nested,
+            _ => return self.meta_mismatch(meta),
+        };
 
-        for attr in variant.attrs.iter().filter(|v| v.path.segments.len() == 1) {
-            match attr.path.segments[0].ident.to_string().as_str() {
-                "error_from" => {
-                    let meta = attr.parse_meta().unwrap();
-                    self.impl_error_from(&item_id, variant, &meta);
-                    break
+        for item in list {
+            let method = match item {
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) => v.get_ident(),
+                _ => None,
+            };
+            match method {
+                Some(v) => {
+                    if v == "variant_name" {
+                        self.variant_name = true;
+                    }
+                    self.delegate_list.push(v.clone());
                 }
-                "error_kind" => {
-                    let meta = attr.parse_meta().unwrap();
-                    self.impl_error_kind(&item_id, variant, &meta);
-                    break
+                None => {
+                    let message = "error_delegate accepts list of accessor names";
+                    self.error_list.push(syn::Error::new_spanned(item, message));
                 }
-                _ => {},
             }
         }
     }
 
-    fn build(&mut self, data: &syn::DataEnum) -> TokenStream {
-        for variant in &data.variants {
-            self.impl_variant(variant);
+    fn set_remote(&mut self, attr: &syn::Attribute) {
+        match attr.parse_args::<syn::Path>() {
+            Ok(v) => self.remote = Some(v),
+            Err(_) => self.meta_mismatch(attr),
         }
+    }
 
-        let enum_id = &self.enum_id;
-        let display_list = &self.display_list;
-        let source_list = &self.source_list;
-        let from_list = &self.from_list;
-
-        let mut display_prefix = TokenStream::new();
-        if ! self.prefix.is_empty() {
+    fn prefix_expr(&self) -> TokenStream {
+        if self.prefix_module {
+            quote! { module_path!().rsplit("::").next().unwrap_or_default() }
+        } else {
             let prefix = &self.prefix;
-            display_prefix.extend(quote! {
-                write!(f, "{}: ", #prefix)?;
-            });
+            quote! { #prefix }
         }
+    }
 
-        quote! {
-            impl std::fmt::Display for #enum_id {
-                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                    #display_prefix
-                    match self {
-                        #display_list
+    fn set_format(&mut self, meta: &syn::Meta) {
+        if let syn::Meta::List(v) = meta {
+            if v.nested.len() == 1 {
+                if let syn::NestedMeta::Lit(syn::Lit::Str(v)) = &v.nested[0] {
+                    if let Err(message) = expand_format(&v.value(), "", "") {
+                        self.error_list.push(syn::Error::new(v.span(), message));
                     }
+                    self.format = Some(v.clone());
+                    return
                 }
             }
+        }
+        self.meta_mismatch(meta)
+    }
 
-            impl std::error::Error for #enum_id {
-                fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-                    match self {
-                        #source_list
-                        _ => None,
-                    }
-                }
+    fn set_trace(&mut self, meta: &syn::Meta) {
+        let level = match meta {
+            syn::Meta::Path(_) => "error".to_owned(),
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(v)) if v.path.is_ident("level") => match &v.lit {
+                    syn::Lit::Str(v) => v.value(),
+                    _ => return self.meta_mismatch(meta),
+                },
+                _ => return self.meta_mismatch(meta),
+            },
+            _ => return self.meta_mismatch(meta),
+        };
+
+        match level.as_str() {
+            "trace" | "debug" | "info" | "warn" | "error" => {
+                let level = Ident::new(&level.to_uppercase(), Span::call_site());
+                self.trace = Some(level);
             }
+            _ => {
+                let message = "error_trace level should be one of: trace, debug, info, warn, error";
+                self.error_list.push(syn::Error::new_spanned(meta, message));
+            }
+        }
+    }
+
+    fn set_py(&mut self, attr: &syn::Attribute) {
+        if ! self.check_feature(attr, cfg!(feature = "pyo3"), "pyo3") {
+            return
+        }
+        self.pyo3 = true;
+
+        let category_id = Ident::new(&format!("{}Category", self.enum_id), Span::call_site());
+        let list = match self.check(attr.parse_meta()) {
+            Some(syn::Meta::Path(_)) | None => return,
+            Some(syn::Meta::List(v)) => v,
+            Some(meta) => return self.meta_mismatch(meta),
+        };
 
-            impl From<#enum_id> for std::io::Error {
-                fn from(error: #enum_id) -> Self {
-                    Self::new(std::io::ErrorKind::Other, error)
+        for item in list.nested.iter() {
+            let (category, exception) = match item {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(v)) => match (v.path.get_ident(), &v.lit) {
+                    (Some(category), syn::Lit::Str(exception)) => (category, exception),
+                    _ => {
+                        self.meta_mismatch(item);
+                        continue
+                    }
+                },
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) => {
+                    self.py_default = Some(py_exception(v));
+                    continue
+                }
+                _ => {
+                    self.meta_mismatch(item);
+                    continue
                 }
+            };
+            let exception = match self.check(exception.parse()) {
+                Some(v) => py_exception(&v),
+                None => continue,
+            };
+            self.py_category_list.extend(quote! {
+                Some(#category_id::#category) => #exception::new_err(message),
+            });
+        }
+    }
+
+    fn set_eq(&mut self, meta: &syn::Meta) {
+        self.eq_kind = true;
+        self.eq = true;
+        match meta {
+            syn::Meta::Path(_) => {}
+            syn::Meta::List(v) if v.nested.len() == 1 => match &v.nested[0] {
+                syn::NestedMeta::Meta(syn::Meta::Path(v)) if v.is_ident("Eq") => self.eq_total = true,
+                _ => self.meta_mismatch(meta),
             }
+            _ => self.meta_mismatch(meta),
+        }
+    }
 
-            #from_list
+    fn set_result(&mut self, meta: &syn::Meta) {
+        if let syn::Meta::NameValue(v) = meta {
+            if let syn::Lit::Str(v) = &v.lit {
+                self.result = Some(v.value());
+                return
+            }
         }
+        self.meta_mismatch(meta)
     }
 
-    fn set_attrs(&mut self, attrs: &Vec<syn::Attribute>) {
+    fn set_attrs(&mut self, attrs: &[syn::Attribute]) {
         for attr in attrs.iter().filter(|v| v.path.segments.len() == 1) {
             match attr.path.segments[0].ident.to_string().as_str() {
-                "error_prefix" => {
-                    if let syn::Meta::NameValue(v) = &attr.parse_meta().unwrap() {
-                        if let syn::Lit::Str(v) = &v.lit {
-                            self.prefix = v.value();
-                            break
-                        }
+                "error_prefix" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.set_prefix(&meta)
+                }
+                "error_remote" => self.set_remote(attr),
+                "error_format" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.set_format(&meta)
+                }
+                "error_audit" => self.audit = true,
+                "error_eq_kind" => self.eq_kind = true,
+                "error_eq" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.set_eq(&meta)
+                }
+                "error_snapshot" => self.snapshot = true,
+                "error_io_class" => self.io_class = true,
+                "error_map" => self.map = true,
+                "error_delegate" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.set_delegate(&meta)
+                }
+                "error_constructors" => self.constructors = true,
+                "error_accessors" => self.accessors = true,
+                "error_kind_enum" => self.kind_enum = true,
+                "error_termination" => self.termination = true,
+                "error_ffi" => self.ffi = true,
+                "error_no_std" => self.no_std = true,
+                "error_py" => self.set_py(attr),
+                "error_trace" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.set_trace(&meta)
+                }
+                "error_variant_name" => self.variant_name = true,
+                "error_fingerprint" => self.fingerprint = true,
+                "error_chain" => self.chain = true,
+                "error_serialize" => {
+                    if ! self.check_feature(attr, cfg!(feature = "serde"), "serde") {
+                        continue
+                    }
+                    self.serialize = true;
+                }
+                "error_deserialize" => {
+                    if ! self.check_feature(attr, cfg!(feature = "serde"), "serde") {
+                        continue
+                    }
+                    if ! self.generics.params.is_empty() {
+                        let message = "error_deserialize is not supported for generic types";
+                        self.error_list.push(syn::Error::new_spanned(attr, message));
+                        continue
+                    }
+                    self.serialize = true;
+                    self.deserialize = true;
+                }
+                "error_diagnostic" => {
+                    if ! self.check_feature(attr, cfg!(feature = "miette"), "miette") {
+                        continue
+                    }
+                    self.diagnostic = true;
+                }
+                "error_or_kind" => self.or_kind = true,
+                "error_sink" => self.sink = true,
+                "error_examples" => self.examples = true,
+                "error_log" => {
+                    if ! self.check_feature(attr, cfg!(feature = "log"), "log") {
+                        continue
+                    }
+                    self.log = true;
+                }
+                "error_report" => {
+                    if ! self.check_feature(attr, cfg!(feature = "error-stack"), "error-stack") {
+                        continue
+                    }
+                    self.report = true;
+                }
+                "error_wasm" => {
+                    if ! self.check_feature(attr, cfg!(feature = "wasm"), "wasm") {
+                        continue
+                    }
+                    self.wasm = true;
+                }
+                "error_axum" => {
+                    if ! self.check_feature(attr, cfg!(feature = "axum"), "axum") {
+                        continue
+                    }
+                    self.axum = true;
+                    self.problem_json = true;
+                }
+                "error_crash_report" => {
+                    if ! self.check_feature(attr, cfg!(feature = "crash-report"), "crash-report") {
+                        continue
                     }
-                    panic!("meta format mismatch")
+                    self.crash_report = true;
+                }
+                "error_syslog" => {
+                    if ! self.check_feature(attr, cfg!(feature = "syslog"), "syslog") {
+                        continue
+                    }
+                    self.syslog = true;
+                }
+                "error_problem_json" => {
+                    if ! self.check_feature(attr, cfg!(feature = "problem-json"), "problem-json") {
+                        continue
+                    }
+                    self.problem_json = true;
+                }
+                "error_grpc" => {
+                    if ! self.check_feature(attr, cfg!(feature = "tonic"), "tonic") {
+                        continue
+                    }
+                    self.tonic = true;
+                    if let Ok(syn::Meta::List(_)) = attr.parse_meta() {
+                        self.grpc_default = self.check(attr.parse_meta().and_then(|v| parse_kind(&v)));
+                    }
+                }
+                "error_defmt" => {
+                    if ! self.check_feature(attr, cfg!(feature = "defmt"), "defmt") {
+                        continue
+                    }
+                    self.defmt = true;
+                }
+                "error_result" => if let Some(meta) = self.check(attr.parse_meta()) {
+                    self.set_result(&meta)
+                }
+                "error_truncate" => {
+                    self.truncate = self.check(attr.parse_meta().and_then(|v| parse_truncate(&v)));
                 }
                 _ => {},
             }
         }
+
+        if let Some(format) = &self.format {
+            let value = format.value();
+            if fmt_has_named(&value, "prefix") && self.prefix.is_empty() && ! self.prefix_module {
+                let message = "error_format {prefix} required error_prefix";
+                self.error_list.push(syn::Error::new(format.span(), message));
+            }
+        }
+
+        if self.no_std {
+            for attr in attrs {
+                self.check_no_std(attr);
+            }
+        }
     }
 }
 
 
-#[proc_macro_derive(Error, attributes(error_from, error_kind, error_prefix))]
+#[proc_macro_derive(Error, attributes(
+    error_from,
+    error_kind,
+    error_prefix,
+    error_remote,
+    error_format,
+    error_audit,
+    error_group,
+    error_truncate,
+    error_eq_kind,
+    error_snapshot,
+    error_timeout,
+    error_cancelled,
+    error_io_class,
+    error_source,
+    error_map,
+    error_transparent,
+    error_builder,
+    error_delegate,
+    error_doc,
+    error_constructors,
+    error_accessors,
+    error_kind_enum,
+    error_result,
+    error_code,
+    error_help,
+    error_backtrace,
+    error_location,
+    error_severity,
+    error_exit_code,
+    error_termination,
+    error_transient,
+    error_category,
+    error_url,
+    error_eq,
+    error_deserialize,
+    error_trace,
+    error_no_std,
+    error_ffi,
+    error_io_kind,
+    error_status,
+    error_grpc,
+    error_py,
+    error_serialize,
+    error_variant_name,
+    error_fingerprint,
+    error_chain,
+    error_into_io,
+    error_diagnostic,
+    error_log,
+    error_report,
+    error_wasm,
+    error_axum,
+    error_defmt,
+    error_or_kind,
+    error_sink,
+    error_examples,
+    error_example,
+    error_problem_json,
+    error_crash_report,
+    error_syslog,
+    error_wire,
+))]
 pub fn error_rules_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as syn::DeriveInput);
 
-    if let syn::Data::Enum(ref s) = input.data {
-        let mut error_rules = ErrorRules::new(&input.ident);
-        error_rules.set_attrs(&input.attrs);
-        error_rules.build(s).into()
-    } else {
-        panic!("enum required")
+    let mut error_rules = ErrorRules::new(&input);
+    error_rules.set_attrs(&input.attrs);
+
+    match &input.data {
+        syn::Data::Enum(s) => error_rules.build(s).into(),
+        syn::Data::Struct(s) => error_rules.build_struct(&input.attrs, s).into(),
+        syn::Data::Union(s) => {
+            let message = "enum or struct required";
+            syn::Error::new_spanned(s.union_token, message).to_compile_error().into()
+        }
     }
 }
+
+
+#[proc_macro_attribute]
+pub fn error_clone(_attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let item = parse_macro_input!(item as syn::DeriveInput);
+
+    error_clone::impl_error_clone(item).into()
+}
+
+
+#[proc_macro_attribute]
+pub fn map_errors(attr: proc_macro::TokenStream, item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let error = parse_macro_input!(attr as syn::Type);
+    let item = parse_macro_input!(item as syn::Item);
+
+    map_errors::impl_map_errors(error, item).into()
+}
+
+
+#[proc_macro]
+pub fn merge_errors(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as merge_errors::MergeErrors);
+
+    merge_errors::impl_merge_errors(input).into()
+}
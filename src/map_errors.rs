@@ -0,0 +1,100 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    self,
+    visit_mut::{
+        self,
+        VisitMut,
+    },
+};
+
+
+/// Rewrites `expr?` into `__MapErrors::__map_errors(expr)?`.
+/// Trait converts error of the `Result` into the given type
+/// and keeps `Option` unchanged
+struct MapErrors {
+    error: syn::Type,
+}
+
+
+impl MapErrors {
+    fn visit_fn_block_mut(&mut self, block: &mut syn::Block) {
+        self.visit_block_mut(block);
+
+        let error = &self.error;
+        let map_trait: syn::Stmt = syn::parse_quote! {
+            #[allow(dead_code)]
+            trait __MapErrors {
+                type Output;
+                fn __map_errors(self) -> Self::Output;
+            }
+        };
+        let map_result: syn::Stmt = syn::parse_quote! {
+            impl<__T, __E> __MapErrors for ::core::result::Result<__T, __E>
+            where
+                #error: ::core::convert::From<__E>,
+            {
+                type Output = ::core::result::Result<__T, #error>;
+
+                #[inline]
+                fn __map_errors(self) -> Self::Output {
+                    self.map_err(<#error>::from)
+                }
+            }
+        };
+        let map_option: syn::Stmt = syn::parse_quote! {
+            impl<__T> __MapErrors for ::core::option::Option<__T> {
+                type Output = Self;
+
+                #[inline]
+                fn __map_errors(self) -> Self {
+                    self
+                }
+            }
+        };
+
+        block.stmts.splice(0 .. 0, vec![map_trait, map_result, map_option]);
+    }
+}
+
+
+impl VisitMut for MapErrors {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        visit_mut::visit_expr_mut(self, expr);
+
+        if let syn::Expr::Try(v) = expr {
+            let inner = &v.expr;
+            let inner: syn::Expr = syn::parse_quote! {
+                __MapErrors::__map_errors(#inner)
+            };
+            *v.expr = inner;
+        }
+    }
+
+    // `?` inside closures, async blocks and nested items
+    // belongs to them and keeps the original conversion
+    fn visit_expr_closure_mut(&mut self, _: &mut syn::ExprClosure) {}
+
+    fn visit_expr_async_mut(&mut self, _: &mut syn::ExprAsync) {}
+
+    fn visit_item_mut(&mut self, _: &mut syn::Item) {}
+}
+
+
+pub fn impl_map_errors(error: syn::Type, mut item: syn::Item) -> TokenStream {
+    let mut map_errors = MapErrors { error };
+
+    match &mut item {
+        syn::Item::Fn(v) => map_errors.visit_fn_block_mut(&mut v.block),
+        syn::Item::Impl(v) => {
+            for item in &mut v.items {
+                if let syn::ImplItem::Method(v) = item {
+                    map_errors.visit_fn_block_mut(&mut v.block);
+                }
+            }
+        }
+        _ => panic!("function or impl block required"),
+    }
+
+    quote! { #item }
+}
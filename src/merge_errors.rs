@@ -0,0 +1,124 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    self,
+    parse::{
+        Parse,
+        ParseStream,
+    },
+};
+
+
+/// `#[attrs] vis Name = Type + Variant: Type + ...`
+pub struct MergeErrors {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    types: Vec<(Option<syn::Ident>, syn::Type)>,
+}
+
+
+/// `Type` or `Variant: Type`
+fn parse_type(input: ParseStream) -> syn::Result<(Option<syn::Ident>, syn::Type)> {
+    let variant_id = if input.peek(syn::Ident) && input.peek2(syn::Token![:]) && ! input.peek2(syn::Token![::]) {
+        let variant_id = input.parse()?;
+        input.parse::<syn::Token![:]>()?;
+        Some(variant_id)
+    } else {
+        None
+    };
+
+    Ok((variant_id, syn::Type::without_plus(input)?))
+}
+
+
+impl Parse for MergeErrors {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+
+        let mut types = vec![parse_type(input)?];
+        while input.parse::<Option<syn::Token![+]>>()?.is_some() {
+            types.push(parse_type(input)?);
+        }
+
+        Ok(MergeErrors { attrs, vis, ident, types })
+    }
+}
+
+
+/// Variant name from the type name.
+/// For `module::Error` variant named by the module: `io::Error` is `Io`
+fn variant_ident(ty: &syn::Type) -> syn::Result<syn::Ident> {
+    let path = match ty {
+        syn::Type::Path(v) if v.qself.is_none() => &v.path,
+        _ => return Err(syn::Error::new_spanned(ty,
+            "error type should be a path, use `Name: Type` to define variant name")),
+    };
+
+    let mut segments = path.segments.iter().rev();
+    let ident = &segments.next().unwrap().ident;
+    match segments.next() {
+        Some(module) if ident == "Error" => {
+            let name: String = module.ident.to_string()
+                .split('_')
+                .filter(|v| ! v.is_empty())
+                .map(|v| {
+                    let mut chars = v.chars();
+                    let first = chars.next().unwrap().to_ascii_uppercase();
+                    std::iter::once(first).chain(chars).collect::<String>()
+                })
+                .collect();
+            Ok(syn::Ident::new(&name, module.ident.span()))
+        }
+        _ => Ok(ident.clone()),
+    }
+}
+
+
+pub fn impl_merge_errors(merge: MergeErrors) -> TokenStream {
+    let MergeErrors { attrs, vis, ident, types } = merge;
+
+    let mut variant_list = TokenStream::new();
+    let mut name_list: Vec<String> = Vec::new();
+    let mut error_list: Vec<syn::Error> = Vec::new();
+    for (variant_id, ty) in &types {
+        let variant_id = match variant_id {
+            Some(v) => v.clone(),
+            None => match variant_ident(ty) {
+                Ok(v) => v,
+                Err(e) => {
+                    error_list.push(e);
+                    continue
+                }
+            },
+        };
+
+        let name = variant_id.to_string();
+        if name_list.contains(&name) {
+            let message = format!("variant {} already defined, use `Name: Type` to define variant name", name);
+            error_list.push(syn::Error::new_spanned(ty, message));
+            continue
+        }
+        name_list.push(name);
+
+        variant_list.extend(quote! {
+            #[error_transparent]
+            #variant_id ( #ty ),
+        });
+    }
+
+    if ! error_list.is_empty() {
+        return error_list.iter().map(syn::Error::to_compile_error).collect()
+    }
+
+    quote! {
+        #[derive(Debug, error_rules::Error)]
+        #( #attrs )*
+        #vis enum #ident {
+            #variant_list
+        }
+    }
+}
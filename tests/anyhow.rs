@@ -0,0 +1,95 @@
+use error_rules::*;
+
+
+/// `anyhow::Error` boxing any error or message, as much as the tests need
+mod anyhow {
+    use std::{
+        error,
+        fmt,
+        ops,
+    };
+
+    pub struct Error(Box<dyn error::Error + Send + Sync + 'static>);
+
+    impl Error {
+        pub fn msg<M>(message: M) -> Self
+        where
+            M: fmt::Display + fmt::Debug + Send + Sync + 'static,
+        {
+            Error(Box::new(MessageError(message)))
+        }
+    }
+
+    /// Error from the `Error::msg()`
+    struct MessageError<M>(M);
+
+    impl<M: fmt::Debug> fmt::Debug for MessageError<M> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl<M: fmt::Display> fmt::Display for MessageError<M> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl<M: fmt::Display + fmt::Debug> error::Error for MessageError<M> {}
+
+    impl fmt::Debug for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl ops::Deref for Error {
+        type Target = dyn error::Error + Send + Sync + 'static;
+
+        fn deref(&self) -> &Self::Target {
+            &*self.0
+        }
+    }
+}
+
+
+#[test]
+fn test_anyhow() {
+    use anyhow::Error;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    enum AppError {
+        #[error_from]
+        Io(std::io::Error),
+        #[error_from]
+        Other(Error),
+    }
+
+    let error: AppError = Error::msg("failed").into();
+    assert_eq!(error.to_string().as_str(), "App: failed");
+    assert_eq!(std::error::Error::source(&error).unwrap().to_string().as_str(), "failed");
+
+    let error: AppError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+    assert_eq!(std::error::Error::source(&error).unwrap().to_string().as_str(), "entity not found");
+}
+
+
+#[test]
+fn test_anyhow_transparent() {
+    #[derive(Debug, Error)]
+    enum AppError {
+        #[error_transparent]
+        Other(anyhow::Error),
+    }
+
+    let error: AppError = anyhow::Error::msg("failed").into();
+    assert_eq!(error.to_string().as_str(), "failed");
+    assert!(std::error::Error::source(&error).is_none());
+}
@@ -0,0 +1,142 @@
+#![cfg(feature = "axum")]
+
+use error_rules::*;
+
+
+/// `axum` types used by the generated `IntoResponse` implementation.
+/// `Response` body is a `String` instead of the streaming `Body`
+mod axum {
+    pub mod http {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct StatusCode(u16);
+
+        #[derive(Debug)]
+        pub struct InvalidStatusCode;
+
+        impl StatusCode {
+            pub const INTERNAL_SERVER_ERROR: StatusCode = StatusCode(500);
+
+            pub fn from_u16(code: u16) -> Result<StatusCode, InvalidStatusCode> {
+                if (100 .. 1000).contains(&code) {
+                    Ok(StatusCode(code))
+                } else {
+                    Err(InvalidStatusCode)
+                }
+            }
+
+            pub fn as_u16(&self) -> u16 {
+                self.0
+            }
+        }
+
+        pub mod header {
+            #[derive(Debug, Clone, Copy, PartialEq)]
+            pub struct HeaderName(&'static str);
+
+            #[derive(Debug, Clone, PartialEq)]
+            pub struct HeaderValue(&'static str);
+
+            #[derive(Debug)]
+            pub struct ToStrError;
+
+            impl HeaderValue {
+                pub fn from_static(src: &'static str) -> HeaderValue {
+                    HeaderValue(src)
+                }
+
+                pub fn to_str(&self) -> Result<&str, ToStrError> {
+                    Ok(self.0)
+                }
+            }
+
+            #[derive(Debug, Default)]
+            pub struct HeaderMap(Vec<(HeaderName, HeaderValue)>);
+
+            impl HeaderMap {
+                pub fn get(&self, key: HeaderName) -> Option<&HeaderValue> {
+                    self.0.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+                }
+
+                pub fn insert(&mut self, key: HeaderName, value: HeaderValue) -> Option<HeaderValue> {
+                    let prev = self.0.iter().position(|(k, _)| *k == key).map(|i| self.0.remove(i).1);
+                    self.0.push((key, value));
+                    prev
+                }
+            }
+
+            pub const CONTENT_TYPE: HeaderName = HeaderName("content-type");
+        }
+    }
+
+    pub mod response {
+        use super::http::{
+            StatusCode,
+            header::{
+                HeaderMap,
+                HeaderName,
+                HeaderValue,
+            },
+        };
+
+        pub struct Response {
+            status: StatusCode,
+            headers: HeaderMap,
+            body: String,
+        }
+
+        impl Response {
+            pub fn status(&self) -> StatusCode {
+                self.status
+            }
+
+            pub fn headers(&self) -> &HeaderMap {
+                &self.headers
+            }
+
+            pub fn body(&self) -> &String {
+                &self.body
+            }
+        }
+
+        pub trait IntoResponse {
+            fn into_response(self) -> Response;
+        }
+
+        impl<const N: usize> IntoResponse for (StatusCode, [(HeaderName, &'static str); N], String) {
+            fn into_response(self) -> Response {
+                let (status, list, body) = self;
+                let mut headers = HeaderMap::default();
+                for (k, v) in list {
+                    headers.insert(k, HeaderValue::from_static(v));
+                }
+                Response { status, headers, body }
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_into_response() {
+    use axum::response::IntoResponse;
+
+    #[derive(Debug, Error)]
+    #[error_axum]
+    enum AppError {
+        #[error_kind("user not found")]
+        #[error_status(404)]
+        NotFound,
+        #[error_kind("internal")]
+        Internal,
+    }
+
+    let response = AppError::NotFound.into_response();
+    assert_eq!(response.status().as_u16(), 404);
+    let content_type = response.headers().get(axum::http::header::CONTENT_TYPE).unwrap();
+    assert_eq!(content_type.to_str().unwrap(), "application/problem+json");
+    assert_eq!(response.body().as_str(),
+        r#"{"type":"about:blank","title":"NotFound","status":404,"detail":"user not found"}"#);
+
+    let response = AppError::Internal.into_response();
+    assert_eq!(response.status(), axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "crash-report")]
+
+use error_rules::*;
+
+
+#[test]
+fn test_crash_report() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    #[error_crash_report]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    let path = e.write_crash_report(&[("config", "key = value")]).unwrap();
+    let report = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(report.contains("name: error-rules\n"));
+    assert!(report.contains("error: App: permission denied\n"));
+    assert!(report.contains("caused by: permission denied\n"));
+    assert!(report.contains("config:\nkey = value\n"));
+    assert!(report.contains("backtrace:\n"));
+}
+
+
+#[test]
+fn test_crash_report_opt_in() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    impl E {
+        fn crash_report(&self) -> &'static str { "custom" }
+    }
+
+    assert_eq!(E::Failed.crash_report(), "custom");
+}
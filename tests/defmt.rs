@@ -0,0 +1,84 @@
+#![cfg(feature = "defmt")]
+
+use error_rules::*;
+
+
+/// `defmt::Format` and `defmt::write!` writing into the string
+/// instead of the interned frames. `to_string()` helps to check the output
+mod defmt {
+    use std::cell::RefCell;
+
+    #[derive(Clone, Copy)]
+    pub struct Formatter<'a>(pub &'a RefCell<String>);
+
+    pub trait Format {
+        fn format(&self, f: Formatter<'_>);
+    }
+
+    impl Format for str {
+        fn format(&self, f: Formatter<'_>) {
+            f.0.borrow_mut().push_str(self)
+        }
+    }
+
+    impl Format for u8 {
+        fn format(&self, f: Formatter<'_>) {
+            f.0.borrow_mut().push_str(&self.to_string())
+        }
+    }
+
+    impl<T: Format + ?Sized> Format for &T {
+        fn format(&self, f: Formatter<'_>) {
+            (**self).format(f)
+        }
+    }
+
+    macro_rules! write {
+        ($f:expr, $fmt:literal $( , $arg:expr )*) => {
+            $crate::defmt::write_fmt($f, $fmt, &[ $( &$arg as &dyn $crate::defmt::Format ),* ])
+        };
+    }
+
+    pub(crate) use write;
+
+    /// Writes text of the format string and arguments in place of the placeholders
+    pub fn write_fmt(f: Formatter<'_>, fmt: &str, arg_list: &[&dyn Format]) {
+        let mut arg_list = arg_list.iter();
+        let mut chars = fmt.chars();
+        while let Some(c) = chars.next() {
+            if c == '{' {
+                chars.by_ref().take_while(|&c| c != '}').for_each(drop);
+                arg_list.next().unwrap().format(f);
+            } else {
+                f.0.borrow_mut().push(c);
+            }
+        }
+    }
+
+    pub fn to_string<T: Format>(value: &T) -> String {
+        let text = RefCell::new(String::new());
+        value.format(Formatter(&text));
+        text.into_inner()
+    }
+}
+
+
+#[test]
+fn test_defmt() {
+    #[derive(Debug, Error)]
+    #[error_no_std]
+    #[error_defmt]
+    #[error_prefix = "Sensor"]
+    enum SensorError {
+        #[error_kind("timeout on channel {}", 0)]
+        Timeout(u8),
+        #[error_kind("channel {channel} failed")]
+        Failed { channel: u8 },
+        #[error_kind("calibration {}", 0.len())]
+        Calibration(Vec<u8>),
+    }
+
+    assert_eq!(defmt::to_string(&SensorError::Timeout(1)).as_str(), "Sensor: timeout on channel 1");
+    assert_eq!(defmt::to_string(&SensorError::Failed { channel: 2 }).as_str(), "Sensor: channel 2 failed");
+    assert_eq!(defmt::to_string(&SensorError::Calibration(vec![1])).as_str(), "Sensor: Calibration");
+}
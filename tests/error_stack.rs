@@ -0,0 +1,110 @@
+#![cfg(feature = "error-stack")]
+
+use error_rules::*;
+
+
+/// `error_stack::Report` with printable attachments only.
+/// Frames iterated from the last attachment to the context like in `error-stack`
+mod error_stack {
+    use std::{
+        error::Error,
+        fmt,
+    };
+
+    pub enum AttachmentKind<'f> {
+        Printable(&'f dyn fmt::Display),
+    }
+
+    pub enum FrameKind<'f> {
+        Context(&'f (dyn Error + Send + Sync + 'static)),
+        Attachment(AttachmentKind<'f>),
+    }
+
+    pub struct Frame(Box<dyn fmt::Display + Send + Sync>);
+
+    impl Frame {
+        pub fn kind(&self) -> FrameKind<'_> {
+            FrameKind::Attachment(AttachmentKind::Printable(&*self.0))
+        }
+    }
+
+    pub struct Report<C> {
+        context: C,
+        attachments: Vec<Frame>,
+    }
+
+    impl<C: Error + Send + Sync + 'static> Report<C> {
+        pub fn new(context: C) -> Self {
+            Report { context, attachments: Vec::new() }
+        }
+
+        pub fn attach_printable<A>(mut self, attachment: A) -> Self
+        where
+            A: fmt::Display + fmt::Debug + Send + Sync + 'static,
+        {
+            self.attachments.push(Frame(Box::new(attachment)));
+            self
+        }
+
+        pub fn current_context(&self) -> &C {
+            &self.context
+        }
+
+        pub fn frames(&self) -> impl Iterator<Item = FrameKind<'_>> {
+            self.attachments.iter().rev()
+                .map(Frame::kind)
+                .chain(std::iter::once(FrameKind::Context(&self.context)))
+        }
+    }
+}
+
+
+/// Printable attachments of the report in order of attaching
+fn attachments<C>(report: &error_stack::Report<C>) -> Vec<String>
+where
+    C: std::error::Error + Send + Sync + 'static,
+{
+    use error_stack::{
+        AttachmentKind,
+        FrameKind,
+    };
+
+    let mut list: Vec<String> = report.frames()
+        .filter_map(|v| match v {
+            FrameKind::Attachment(AttachmentKind::Printable(v)) => Some(v.to_string()),
+            FrameKind::Context(_) => None,
+        })
+        .collect();
+    list.reverse();
+    list
+}
+
+
+#[test]
+fn test_into_report() {
+    #[derive(Debug, Error)]
+    #[error_report]
+    enum AppError {
+        #[error_kind("config not found")]
+        #[error_code = 100]
+        #[error_help("create config with --init")]
+        #[error_url("https://example.com/kb/config")]
+        Config,
+        #[error_kind("closed")]
+        #[error_code = 200]
+        Closed,
+    }
+
+    let report = AppError::Config.into_report();
+    assert_eq!(report.current_context().to_string().as_str(), "config not found");
+    assert!(matches!(report.frames().last(),
+        Some(error_stack::FrameKind::Context(v)) if v.to_string() == "config not found"));
+    assert_eq!(attachments(&report), vec![
+        "code: 100".to_owned(),
+        "help: create config with --init".to_owned(),
+        "see: https://example.com/kb/config".to_owned(),
+    ]);
+
+    let report = AppError::Closed.into_report();
+    assert_eq!(attachments(&report), vec!["code: 200".to_owned()]);
+}
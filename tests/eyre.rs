@@ -0,0 +1,108 @@
+use error_rules::*;
+
+
+/// `eyre::Report` with `?` conversion and `chain()` over the error sources
+mod eyre {
+    use std::{
+        error,
+        fmt,
+        ops,
+    };
+
+    pub struct Report(Box<dyn error::Error + Send + Sync + 'static>);
+
+    pub type Result<T> = std::result::Result<T, Report>;
+
+    impl Report {
+        pub fn msg<M>(message: M) -> Self
+        where
+            M: fmt::Display + fmt::Debug + Send + Sync + 'static,
+        {
+            Report(Box::new(MessageError(message)))
+        }
+
+        /// Iterator over the error and its sources
+        pub fn chain(&self) -> impl Iterator<Item = &(dyn error::Error + 'static)> {
+            let mut source: Option<&(dyn error::Error + 'static)> = Some(&*self.0);
+            std::iter::from_fn(move || {
+                let e = source?;
+                source = e.source();
+                Some(e)
+            })
+        }
+    }
+
+    impl<E: error::Error + Send + Sync + 'static> From<E> for Report {
+        fn from(e: E) -> Self {
+            Report(Box::new(e))
+        }
+    }
+
+    /// Error from the `Report::msg()`
+    struct MessageError<M>(M);
+
+    impl<M: fmt::Debug> fmt::Debug for MessageError<M> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl<M: fmt::Display> fmt::Display for MessageError<M> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl<M: fmt::Display + fmt::Debug> error::Error for MessageError<M> {}
+
+    impl fmt::Debug for Report {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl fmt::Display for Report {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt::Display::fmt(&self.0, f)
+        }
+    }
+
+    impl ops::Deref for Report {
+        type Target = dyn error::Error + Send + Sync + 'static;
+
+        fn deref(&self) -> &Self::Target {
+            &*self.0
+        }
+    }
+}
+
+
+#[test]
+fn test_eyre() {
+    use eyre::Report;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    enum AppError {
+        #[error_from]
+        Io(std::io::Error),
+        #[error_from]
+        Other(Report),
+    }
+
+    fn run() -> eyre::Result<()> {
+        Err(AppError::from(Report::msg("failed")))?
+    }
+
+    let report = run().unwrap_err();
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, vec!["App: failed".to_owned(), "failed".to_owned()]);
+
+    fn read() -> eyre::Result<()> {
+        Err(AppError::from(std::io::Error::from(std::io::ErrorKind::NotFound)))?
+    }
+
+    let report = read().unwrap_err();
+    let chain: Vec<String> = report.chain().map(ToString::to_string).collect();
+    assert_eq!(chain, vec!["App: entity not found".to_owned(), "entity not found".to_owned()]);
+}
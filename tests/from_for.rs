@@ -11,6 +11,8 @@ use error_rules::*;
 pub enum TestSError {
     #[error_from]
     IO(io::Error),
+    #[error_from]
+    Utf8(std::str::Utf8Error),
     #[error_kind("Test kind")]
     TestKind,
 }
@@ -24,6 +26,7 @@ struct TestS {}
 
 
 impl TestS {
+    #[allow(clippy::unused_io_amount)]
     fn test_io_error(&mut self) -> Result<()> {
         let mut buf = [0; 1];
         self.read(&mut buf)?;
@@ -50,3 +53,84 @@ fn for_from_test() {
     assert!(tests.test_kind_error().is_err());
     assert!(tests.test_io_error().is_err());
 }
+
+
+struct TestM {
+    data: Vec<u8>,
+}
+
+
+#[map_errors(TestSError)]
+impl Read for TestM {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let text = std::str::from_utf8(&self.data)?;
+        buf[.. text.len()].copy_from_slice(text.as_bytes());
+        Ok(text.len())
+    }
+}
+
+
+#[test]
+fn map_errors_test() {
+    let mut testm = TestM { data: vec![0xFF] };
+    let mut buf = [0; 1];
+    let e = testm.read(&mut buf).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::Other);
+    assert!(e.to_string().starts_with("Test Prefix: invalid utf-8"));
+}
+
+
+#[derive(Debug, Error)]
+pub enum ReadError {
+    #[error_from]
+    #[error_into_io]
+    IO(io::Error),
+    #[error_from]
+    Utf8(std::str::Utf8Error),
+}
+
+
+struct TestIo {
+    data: Vec<u8>,
+}
+
+
+impl TestIo {
+    fn fill(&self) -> std::result::Result<usize, ReadError> {
+        Err(io::Error::from(io::ErrorKind::NotFound).into())
+    }
+}
+
+
+#[map_errors(ReadError)]
+impl Read for TestIo {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let text = std::str::from_utf8(&self.data)?;
+        buf[.. text.len()].copy_from_slice(text.as_bytes());
+        self.fill()?;
+        Ok(text.len())
+    }
+}
+
+
+#[map_errors(ReadError)]
+fn first_line(data: &[u8]) -> Option<&[u8]> {
+    let line = data.split(|v| *v == b'\n').next()?;
+    Some(line)
+}
+
+
+#[test]
+fn map_errors_into_io_test() {
+    let mut buf = [0; 1];
+
+    let e = TestIo { data: vec![b'a'] }.read(&mut buf).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::NotFound);
+    assert!(e.get_ref().is_none());
+
+    let e = TestIo { data: vec![0xFF] }.read(&mut buf).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::Other);
+    assert!(e.get_ref().unwrap().is::<ReadError>());
+
+    assert_eq!(first_line(b"a\nb"), Some(&b"a"[..]));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "log")]
+
+use error_rules::*;
+
+
+/// `log::log!` macro pushing records into the thread local list,
+/// `take()` returns and clears the records
+mod log {
+    use std::cell::RefCell;
+
+    #[derive(Debug)]
+    pub enum Level {
+        Error,
+        Warn,
+    }
+
+    thread_local! {
+        pub static RECORDS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    macro_rules! log {
+        ($level:expr, $($arg:tt)+) => {{
+            let record = format!("{:?} {}", $level, format!($($arg)+));
+            $crate::log::RECORDS.with(|v| v.borrow_mut().push(record))
+        }};
+    }
+
+    pub(crate) use log;
+
+    pub fn take() -> Vec<String> {
+        RECORDS.with(|v| v.borrow_mut().split_off(0))
+    }
+}
+
+
+#[test]
+fn test_log() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_log]
+    #[error_prefix = "App"]
+    enum AppError {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    fn load(ok: bool) -> Result<&'static str, AppError> {
+        if ok {
+            Ok("config")
+        } else {
+            Err(io::Error::from(io::ErrorKind::NotFound))?
+        }
+    }
+
+    assert_eq!(load(true).log_warn().unwrap(), "config");
+    assert!(log::take().is_empty());
+
+    assert!(load(false).log_warn().is_err());
+    assert!(load(false).log_err(log::Level::Error).is_err());
+    assert_eq!(log::take(), vec![
+        "Warn App: entity not found: entity not found".to_owned(),
+        "Error App: entity not found: entity not found".to_owned(),
+    ]);
+}
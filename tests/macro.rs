@@ -13,6 +13,24 @@ fn test_error_kind_wo_arg() {
 }
 
 
+#[test]
+fn test_error_format_prefix() {
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    #[error_format("{prefix}: {variant}: {fields}")]
+    enum E {
+        Tuple(usize, usize),
+        Named { code: usize },
+        #[error_kind("custom")]
+        Custom,
+    }
+
+    assert_eq!(E::Tuple(100, 200).to_string().as_str(), "App: Tuple: 100, 200");
+    assert_eq!(E::Named { code: 100 }.to_string().as_str(), "App: Named: code: 100");
+    assert_eq!(E::Custom.to_string().as_str(), "App: custom");
+}
+
+
 #[test]
 fn test_error_kind_w1_arg() {
     #[derive(Debug, Error)]
@@ -37,6 +55,24 @@ fn test_error_kind_w2_arg() {
 }
 
 
+#[test]
+fn test_error_kind_named() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("custom:{}:{}", code, name)]
+        Custom { name: String, code: usize },
+        #[error_kind("bytes:{}", hex(data))]
+        Bytes { data: Vec<u8> },
+    }
+
+    let e = E::Custom { name: "test".to_owned(), code: 100 };
+    assert_eq!(e.to_string().as_str(), "custom:100:test");
+
+    let e = E::Bytes { data: vec![1, 2] };
+    assert_eq!(e.to_string().as_str(), "bytes:0102");
+}
+
+
 #[test]
 fn test_error_from() {
     use std::io;
@@ -82,3 +118,1913 @@ fn test_error_prefix() {
     let e: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
     assert_eq!(e.to_string().as_str(), "App: permission denied");
 }
+
+
+#[test]
+fn test_error_from_source() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from("io:{source}")]
+        Io(io::Error),
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert_eq!(e.to_string().as_str(), "io:permission denied");
+}
+
+
+#[test]
+fn test_fingerprint() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_fingerprint]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("custom:{}", 0)]
+        Custom(usize),
+    }
+
+    let e1: E = io::Error::new(io::ErrorKind::NotFound, "a.txt").into();
+    let e2: E = io::Error::new(io::ErrorKind::NotFound, "b.txt").into();
+    let e3: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert_eq!(e1.fingerprint(), e2.fingerprint());
+    assert_ne!(e1.fingerprint(), e3.fingerprint());
+
+    assert_eq!(E::Custom(1).fingerprint(), E::Custom(2).fingerprint());
+    assert_ne!(E::Custom(1).fingerprint(), e1.fingerprint());
+}
+
+
+#[test]
+fn test_error_remote() {
+    mod other {
+        #[derive(Debug)]
+        pub enum Error {
+            Closed,
+            Code(usize, usize),
+            Io(std::io::Error),
+        }
+    }
+
+    #[derive(Debug, Error)]
+    #[error_remote(other::Error)]
+    enum E {
+        #[error_kind("closed")]
+        Closed,
+        #[error_kind("code:{}:{}", 0, 1)]
+        Code(usize, usize),
+        #[error_from]
+        Io(std::io::Error),
+    }
+
+    let e: E = other::Error::Closed.into();
+    assert_eq!(e.to_string().as_str(), "closed");
+
+    let e: E = other::Error::Code(100, 200).into();
+    assert_eq!(e.to_string().as_str(), "code:100:200");
+
+    let e: E = other::Error::Io(std::io::ErrorKind::PermissionDenied.into()).into();
+    assert_eq!(e.to_string().as_str(), "permission denied");
+}
+
+
+#[test]
+fn test_error_format() {
+    #[derive(Debug, Error)]
+    #[error_format("{variant}: {fields}")]
+    enum E {
+        Unit,
+        Tuple(usize, usize),
+        Named { code: usize },
+        #[error_kind("custom")]
+        Custom,
+    }
+
+    assert_eq!(E::Unit.to_string().as_str(), "Unit: ");
+    assert_eq!(E::Tuple(100, 200).to_string().as_str(), "Tuple: 100, 200");
+    assert_eq!(E::Named { code: 100 }.to_string().as_str(), "Named: code: 100");
+    assert_eq!(E::Custom.to_string().as_str(), "custom");
+}
+
+
+#[test]
+fn test_error_audit() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_audit]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_from]
+        Utf8(std::string::FromUtf8Error),
+    }
+
+    assert_eq!(E::ERROR_CONVERSIONS, &[
+        ("io::Error", "Io"),
+        ("std::string::FromUtf8Error", "Utf8"),
+    ]);
+}
+
+
+#[test]
+fn test_error_group() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from]
+        #[error_group("storage")]
+        Io(io::Error),
+        #[error_kind("custom")]
+        Custom,
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert_eq!(e.group(), Some("storage"));
+    assert_eq!(e.to_string().as_str(), "storage: permission denied");
+
+    assert_eq!(E::Custom.group(), None);
+    assert_eq!(E::Custom.to_string().as_str(), "custom");
+}
+
+
+#[test]
+fn test_error_truncate() {
+    #[derive(Debug, Error)]
+    #[error_truncate(12)]
+    enum E {
+        #[error_kind("custom:{}", 0)]
+        Custom(String),
+        #[error_truncate(4)]
+        #[error_kind("short:{}", 0)]
+        Short(String),
+    }
+
+    assert_eq!(E::Custom("ok".to_owned()).to_string().as_str(), "custom:ok");
+    assert_eq!(E::Custom("ёжик в тумане".to_owned()).to_string().as_str(), "custom:ёжик ...");
+    assert_eq!(E::Short("ok".to_owned()).to_string().as_str(), "shor...");
+}
+
+
+#[test]
+fn test_error_truncate_field() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("bad input: {}", 0)]
+        BadInput(#[error_truncate(4)] String),
+        #[error_kind("bad name: {name}")]
+        BadName {
+            #[error_truncate(4)]
+            name: String,
+        },
+    }
+
+    assert_eq!(E::BadInput("ok".to_owned()).to_string().as_str(), "bad input: ok");
+    assert_eq!(E::BadInput("ёжик в тумане".to_owned()).to_string().as_str(), "bad input: ёжик...");
+    assert_eq!(E::BadName { name: "test".to_owned() }.to_string().as_str(), "bad name: test");
+    assert_eq!(E::BadName { name: "testing".to_owned() }.to_string().as_str(), "bad name: test...");
+}
+
+
+#[test]
+fn test_error_eq_kind() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_eq_kind]
+    #[error_format("{variant}")]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        Unit,
+        Tuple(usize, String),
+        Named { code: usize },
+    }
+
+    let e1: E = io::Error::from(io::ErrorKind::NotFound).into();
+    let e2: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert!(e1.eq_kind(&e2));
+    assert!(! e1.eq_kind(&E::Unit));
+
+    assert!(E::Unit.eq_kind(&E::Unit));
+    assert!(E::Tuple(1, "a".to_owned()).eq_kind(&E::Tuple(1, "a".to_owned())));
+    assert!(! E::Tuple(1, "a".to_owned()).eq_kind(&E::Tuple(1, "b".to_owned())));
+    assert!(E::Named { code: 1 }.eq_kind(&E::Named { code: 1 }));
+    assert!(! E::Named { code: 1 }.eq_kind(&E::Named { code: 2 }));
+}
+
+
+#[test]
+fn test_error_snapshot() {
+    #[derive(Debug, Error)]
+    #[error_snapshot]
+    enum E {
+        #[error_kind("custom:{}", 0)]
+        Custom(usize),
+    }
+
+    assert_eq!(E::Custom(100).snapshot().as_str(),
+        "type: E\nvariant: Custom\nmessage: custom:100\n");
+}
+
+
+#[test]
+fn test_error_examples() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_examples]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_example]
+        #[error_kind("bad request: {}", 0)]
+        BadRequest(String),
+        #[error_example]
+        #[error_kind("status {}", code)]
+        Status { code: u16 },
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let list: Vec<String> = E::examples().iter().map(ToString::to_string).collect();
+    assert_eq!(list, vec!["bad request: ", "status 0", "closed"]);
+}
+
+
+#[test]
+fn test_error_chain_diff() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_snapshot]
+    enum E {
+        #[error_from("read: {}", 0)]
+        Read(io::Error),
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.chain_diff(&["read: entity not found", "entity not found"]), None);
+    assert_eq!(e.chain_diff(&["read: entity not found", "timed out"]).unwrap().as_str(),
+        "level 1: expected \"timed out\", found \"entity not found\"\n");
+    assert_eq!(e.chain_diff(&["read: entity not found"]).unwrap().as_str(),
+        "level 1: extra \"entity not found\"\n");
+    assert_eq!(e.chain_diff(&["read: entity not found", "entity not found", "os"]).unwrap().as_str(),
+        "level 2: missing \"os\"\n");
+}
+
+
+#[test]
+fn test_error_timeout() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_timeout]
+        #[error_from]
+        Timeout(io::Error),
+        #[error_kind("custom")]
+        Custom,
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::TimedOut).into();
+    assert!(e.is_timeout());
+    assert!(! e.is_cancelled());
+    assert!(! E::Custom.is_timeout());
+}
+
+
+#[test]
+fn test_error_or_kind() {
+    use std::{
+        error::Error as StdError,
+        io,
+    };
+
+    #[derive(Debug, Error)]
+    #[error_or_kind]
+    enum E {
+        #[error_kind("cache miss")]
+        CacheMiss {
+            #[error_source(optional)]
+            source: Option<Box<dyn StdError + Send + Sync>>,
+        },
+        #[error_kind("query {} failed", 0)]
+        Query(String, #[error_source(optional)] Option<Box<dyn StdError + Send + Sync>>),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let r: Result<(), io::Error> = Err(io::ErrorKind::NotFound.into());
+    let e = r.or_kind(E::CacheMiss { source: None }).unwrap_err();
+    assert_eq!(e.to_string().as_str(), "cache miss");
+    assert!(e.source().unwrap().downcast_ref::<io::Error>().is_some());
+
+    let sql = "SELECT 1".to_owned();
+    let r: Result<(), io::Error> = Err(io::ErrorKind::TimedOut.into());
+    let e = r.with_kind(|| E::Query(sql.clone(), None)).unwrap_err();
+    assert_eq!(e.to_string().as_str(), "query SELECT 1 failed");
+    assert_eq!(e.source().unwrap().to_string().as_str(), "timed out");
+
+    let r: Result<(), io::Error> = Err(io::ErrorKind::TimedOut.into());
+    assert!(r.or_kind(E::Closed).unwrap_err().source().is_none());
+
+    let r: Result<u8, io::Error> = Ok(1);
+    assert_eq!(r.or_kind(E::Closed).unwrap(), 1);
+}
+
+
+#[test]
+fn test_error_sink() {
+    use std::{
+        io,
+        sync::mpsc,
+    };
+
+    #[derive(Debug, Error)]
+    #[error_sink]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    let (sink, errors) = mpsc::channel();
+
+    let r: Result<u8, io::Error> = Ok(1);
+    assert_eq!(r.or_report(&sink), Some(1));
+    assert!(errors.try_recv().is_err());
+
+    let r: Result<u8, io::Error> = Err(io::ErrorKind::NotFound.into());
+    assert_eq!(r.or_report(&sink).unwrap_or_default(), 0);
+    assert_eq!(errors.try_recv().unwrap().to_string().as_str(), "entity not found");
+
+    drop(errors);
+    let r: Result<u8, io::Error> = Err(io::ErrorKind::NotFound.into());
+    assert_eq!(r.or_report(&sink), None);
+}
+
+
+#[test]
+fn test_error_timeout_deadline() {
+    use std::{
+        io,
+        time::Duration,
+    };
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_timeout(deadline)]
+        #[error_from(default, "timed out after {:?}", deadline)]
+        Timeout {
+            source: io::Error,
+            deadline: Duration,
+        },
+        #[error_from]
+        Fmt(std::fmt::Error),
+    }
+
+    let r: Result<(), io::Error> = Err(io::Error::from(io::ErrorKind::TimedOut));
+    let e = r.deadline(Duration::from_secs(5)).unwrap_err();
+    assert!(e.is_timeout());
+    assert_eq!(e.to_string().as_str(), "timed out after 5s");
+
+    let r: Result<(), std::fmt::Error> = Err(std::fmt::Error);
+    let e = r.deadline(Duration::from_secs(5)).unwrap_err();
+    assert!(! e.is_timeout());
+}
+
+
+#[test]
+fn test_error_lazy_display() {
+    use std::fmt;
+
+    #[derive(Error)]
+    enum E {
+        #[error_kind("custom:{}:{}", 0, 1)]
+        Custom(usize, Box<dyn Fn(&mut fmt::Formatter) -> fmt::Result + Send + Sync>),
+    }
+
+    impl fmt::Debug for E {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("E")
+        }
+    }
+
+    let e = E::Custom(100, Box::new(|f| write!(f, "{:04}", 200)));
+    assert_eq!(e.to_string().as_str(), "custom:100:0200");
+}
+
+
+#[test]
+fn test_merge_errors() {
+    use std::io;
+
+    mod a {
+        use error_rules::*;
+
+        #[derive(Debug, Error)]
+        #[error_prefix = "A"]
+        pub enum Error {
+            #[error_kind("custom")]
+            Custom,
+        }
+    }
+
+    mod b {
+        use error_rules::*;
+
+        #[derive(Debug, Error)]
+        pub enum Error {
+            #[error_kind("b")]
+            Custom,
+        }
+    }
+
+    merge_errors!(#[error_prefix = "M"] E = a::Error + b::Error + io::Error + Utf8: std::str::Utf8Error);
+
+    let e: E = a::Error::Custom.into();
+    assert!(matches!(e, E::A(_)));
+    assert_eq!(e.to_string().as_str(), "A: custom");
+    assert!(std::error::Error::source(&e).is_none());
+
+    let e: E = b::Error::Custom.into();
+    assert!(matches!(e, E::B(_)));
+
+    let e: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert!(matches!(e, E::Io(_)));
+    assert_eq!(e.to_string().as_str(), "permission denied");
+
+    let data = vec![0xFF];
+    let e: E = std::str::from_utf8(&data).unwrap_err().into();
+    assert!(matches!(e, E::Utf8(_)));
+}
+
+
+#[test]
+fn test_error_bytes_display() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("hex:{}", hex(0))]
+        Hex(&'static [u8]),
+        #[error_kind("hex:{}:{}", 0, hex(1, 2))]
+        HexLimit(usize, [u8; 3]),
+        #[error_kind("utf8:{}", utf8(0))]
+        Utf8(Vec<u8>),
+    }
+
+    assert_eq!(E::Hex(b"\x00\xAB").to_string().as_str(), "hex:00ab");
+    assert_eq!(E::HexLimit(1, [1, 2, 3]).to_string().as_str(), "hex:1:0102... (3 bytes)");
+    assert_eq!(E::Utf8(b"ab\xFF".to_vec()).to_string().as_str(), "utf8:ab\u{FFFD}");
+}
+
+
+#[test]
+fn test_error_io_class() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum Inner {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error_io_class]
+    enum E {
+        #[error_from]
+        Inner(Inner),
+        #[error_kind("custom")]
+        Custom,
+    }
+
+    let e: E = Inner::from(io::Error::from(io::ErrorKind::BrokenPipe)).into();
+    assert_eq!(e.io_class(), Some(EIoClass::ConnectionReset));
+
+    let e: E = Inner::from(io::Error::from(io::ErrorKind::NotFound)).into();
+    assert_eq!(e.io_class(), Some(EIoClass::NotFound));
+
+    assert_eq!(E::Custom.io_class(), None);
+}
+
+
+#[test]
+fn test_error_generics() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "Store"]
+    enum E<B: std::error::Error, T>
+    where
+        T: std::fmt::Display,
+    {
+        #[error_from]
+        Backend(B),
+        #[error_kind("key:{}", 0)]
+        Key(T),
+    }
+
+    let e: E<io::Error, usize> = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert_eq!(e.to_string().as_str(), "Store: permission denied");
+    assert!(std::error::Error::source(&e).is_some());
+
+    let e: E<io::Error, &str> = E::Key("test");
+    assert_eq!(e.to_string().as_str(), "Store: key:test");
+
+    let e: io::Error = E::<io::Error, String>::Key("test".to_owned()).into();
+    assert_eq!(e.to_string().as_str(), "Store: key:test");
+}
+
+
+#[test]
+fn test_error_source_field() {
+    use std::io;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from(default, "Open {}: {}", 0, 1)]
+        Open(String, #[error_source] io::Error),
+        #[error_from(default)]
+        Config(u8, #[error_source] std::num::ParseIntError),
+        #[error_from(default, "Read {}: {source}", path)]
+        Read {
+            path: String,
+            #[error_source]
+            error: std::fmt::Error,
+            _at: Option<PathBuf>,
+        },
+        #[error_from("Parse {}: {}", 0, 1)]
+        Parse(String, #[error_source] std::str::Utf8Error),
+    }
+
+    impl From<std::str::Utf8Error> for E {
+        fn from(e: std::str::Utf8Error) -> Self {
+            E::Parse("utf8".to_owned(), e)
+        }
+    }
+
+    let e = E::Open("/tmp/x".to_owned(), io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(e.to_string().as_str(), "Open /tmp/x: entity not found");
+    assert!(std::error::Error::source(&e).is_some());
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.to_string().as_str(), "Open : entity not found");
+
+    let e: E = "x".parse::<u8>().unwrap_err().into();
+    assert_eq!(e.to_string().as_str(), "invalid digit found in string");
+
+    let e: E = std::fmt::Error.into();
+    assert_eq!(e.to_string().as_str(), "Read : an error occurred when formatting an argument");
+    assert!(std::error::Error::source(&e).is_some());
+
+    let data = vec![0xFF];
+    let e: E = std::str::from_utf8(&data).unwrap_err().into();
+    assert!(e.to_string().starts_with("Parse utf8: invalid utf-8"));
+}
+
+
+#[test]
+fn test_error_from_with() {
+    use std::io;
+
+    struct Errno(i32);
+
+    fn errno_to_io(e: Errno) -> io::Error {
+        io::Error::from_raw_os_error(e.0)
+    }
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from("System: {}", 0, with = "errno_to_io", from = "Errno")]
+        System(io::Error),
+    }
+
+    let e: E = Errno(2).into();
+    assert!(e.to_string().starts_with("System: "));
+    assert!(std::error::Error::source(&e).is_some());
+}
+
+
+#[test]
+fn test_error_from_box() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    enum E {
+        #[error_from]
+        Other(Box<dyn std::error::Error + Send + Sync>),
+    }
+
+    let e: E = Box::<dyn std::error::Error + Send + Sync>::from(io::Error::from(io::ErrorKind::NotFound)).into();
+    assert_eq!(e.to_string().as_str(), "App: entity not found");
+    let source = std::error::Error::source(&e).unwrap();
+    assert!(source.downcast_ref::<io::Error>().is_some());
+
+    let e: io::Error = E::from(Box::<dyn std::error::Error + Send + Sync>::from("test")).into();
+    assert_eq!(e.to_string().as_str(), "App: test");
+}
+
+
+#[test]
+fn test_error_struct() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_from("Config {}: {source}", path)]
+    struct ConfigError {
+        path: String,
+        #[error_source]
+        error: io::Error,
+    }
+
+    #[derive(Debug, Error)]
+    #[error_from("Load {}: {}", path, source)]
+    struct LoadError {
+        path: std::path::PathBuf,
+        source: io::Error,
+    }
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "Parse"]
+    #[error_from]
+    struct ParseError(std::num::ParseIntError);
+
+    #[derive(Debug, Error)]
+    #[error_kind("Closed")]
+    struct ClosedError;
+
+    let e = ConfigError {
+        path: "/etc/app.conf".to_owned(),
+        error: io::Error::from(io::ErrorKind::NotFound),
+    };
+    assert_eq!(e.to_string().as_str(), "Config /etc/app.conf: entity not found");
+    assert!(std::error::Error::source(&e).is_some());
+
+    let e = LoadError {
+        path: "/etc/app.conf".into(),
+        source: io::Error::from(io::ErrorKind::NotFound),
+    };
+    assert_eq!(e.to_string().as_str(), "Load /etc/app.conf: entity not found");
+    assert!(std::error::Error::source(&e).unwrap().is::<io::Error>());
+
+    let e: ParseError = "x".parse::<u8>().unwrap_err().into();
+    assert_eq!(e.to_string().as_str(), "Parse: invalid digit found in string");
+
+    assert_eq!(ClosedError.to_string().as_str(), "Closed");
+}
+
+
+#[test]
+fn test_error_map() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_map]
+    enum E {
+        #[error_from("Open {}: {}", 0, 1)]
+        Open(String, #[error_source] io::Error),
+        #[error_kind("Bad code {}", code)]
+        Code { code: u16 },
+        #[error_kind("Closed")]
+        Closed,
+    }
+
+    let e = E::Open("/home/user/secret".to_owned(), io::Error::from(io::ErrorKind::NotFound))
+        .map_open(|path| *path = "<redacted>".to_owned())
+        .map_code(|code| *code = 0);
+    assert_eq!(e.to_string().as_str(), "Open <redacted>: entity not found");
+    assert!(std::error::Error::source(&e).is_some());
+
+    let e = E::Code { code: 1200 }.map_code(|code| *code /= 100);
+    assert_eq!(e.to_string().as_str(), "Bad code 12");
+
+    let e = E::Closed.map_code(|code| *code = 0);
+    assert_eq!(e.to_string().as_str(), "Closed");
+}
+
+
+#[test]
+fn test_error_transparent() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "Inner"]
+    enum Inner {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    enum E {
+        #[error_transparent]
+        Inner(Inner),
+        #[error_kind("custom")]
+        Custom,
+    }
+
+    let e: E = Inner::from(io::Error::from(io::ErrorKind::NotFound)).into();
+    assert_eq!(e.to_string().as_str(), "Inner: entity not found");
+    let source = std::error::Error::source(&e).unwrap();
+    assert!(source.downcast_ref::<io::Error>().is_some());
+
+    assert_eq!(E::Custom.to_string().as_str(), "App: custom");
+}
+
+
+#[test]
+fn test_error_no_from() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from("Read: {}", 0)]
+        Read(io::Error),
+        #[error_from("Write: {}", 0, no_from)]
+        Write(io::Error),
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.to_string().as_str(), "Read: entity not found");
+
+    let e = E::Write(io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(e.to_string().as_str(), "Write: entity not found");
+    assert!(std::error::Error::source(&e).is_some());
+}
+
+
+#[test]
+fn test_error_builder() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_builder]
+        #[error_from("Query {} failed: {source}", sql)]
+        QueryFailed {
+            sql: String,
+            rows: Option<usize>,
+            #[error_source]
+            error: io::Error,
+        },
+    }
+
+    let e = E::query_failed()
+        .sql("SELECT 1".to_owned())
+        .error(io::Error::from(io::ErrorKind::TimedOut))
+        .build()
+        .unwrap();
+    assert_eq!(e.to_string().as_str(), "Query SELECT 1 failed: timed out");
+    assert!(matches!(e, E::QueryFailed { rows: None, .. }));
+
+    let missing = E::query_failed().sql(String::new()).build().unwrap_err();
+    assert_eq!(missing, EMissingField { builder: "EQueryFailedBuilder", field: "error" });
+    assert_eq!(missing.to_string().as_str(), "EQueryFailedBuilder: field error required");
+}
+
+
+#[test]
+fn test_error_from_primary() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from("Read: {}", 0, primary)]
+        Read(io::Error),
+        #[error_from("Write: {}", 0)]
+        Write(io::Error),
+        #[error_from("Flush: {}", 0)]
+        Flush(io::Error),
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.to_string().as_str(), "Read: entity not found");
+
+    let e = E::Write(io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(e.to_string().as_str(), "Write: entity not found");
+
+    let e = E::Flush(io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(e.to_string().as_str(), "Flush: entity not found");
+}
+
+
+#[test]
+fn test_error_inline_args() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("missing key {key} in section {section}")]
+        MissingKey { key: String, section: String, _line: usize },
+        #[error_kind("bad value {1:?} at {0}")]
+        BadValue(usize, String),
+    }
+
+    let e = E::MissingKey { key: "port".to_owned(), section: "server".to_owned(), _line: 1 };
+    assert_eq!(e.to_string().as_str(), "missing key port in section server");
+
+    let e = E::BadValue(12, "x".to_owned());
+    assert_eq!(e.to_string().as_str(), "bad value \"x\" at 12");
+}
+
+
+#[test]
+fn test_error_delegate() {
+    mod internal {
+        use error_rules::*;
+
+        #[derive(Debug, Error)]
+        #[error_prefix = "Internal"]
+        #[error_variant_name]
+        #[error_kind_enum]
+        pub enum Error {
+            #[error_kind("closed")]
+            #[error_code = 1]
+            #[error_severity(warn)]
+            #[error_category(Network)]
+            Closed,
+            #[error_from]
+            #[error_code = 2]
+            Io(std::io::Error),
+        }
+    }
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "Public"]
+    #[error_delegate(code, variant_name, severity, kind, category)]
+    enum PublicError {
+        Inner(internal::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error_delegate]
+    enum PlainError {
+        Inner(internal::Error),
+    }
+
+    let e: PublicError = internal::Error::Closed.into();
+    assert_eq!(e.to_string().as_str(), "Public: Internal: closed");
+    assert!(std::error::Error::source(&e).is_none());
+    assert_eq!(e.code(), 1);
+    assert_eq!(e.variant_name(), "Closed");
+    assert_eq!(e.severity(), internal::ErrorSeverity::Warn);
+    assert_eq!(e.kind(), internal::ErrorKind::Closed);
+    assert_eq!(e.category(), Some(internal::ErrorCategory::Network));
+
+    let e: PublicError = internal::Error::from(std::io::Error::from(std::io::ErrorKind::NotFound)).into();
+    assert_eq!(e.to_string().as_str(), "Public: Internal: entity not found");
+    assert_eq!(std::error::Error::source(&e).unwrap().to_string().as_str(), "entity not found");
+    assert_eq!(e.code(), 2);
+    assert_eq!(e.variant_name(), "Io");
+    assert_eq!(e.severity(), internal::ErrorSeverity::Error);
+    assert_eq!(e.kind(), internal::ErrorKind::Io);
+    assert_eq!(e.category(), None);
+
+    let e: PlainError = internal::Error::Closed.into();
+    assert_eq!(e.to_string().as_str(), "Internal: closed");
+}
+
+
+#[test]
+fn test_error_expr_args() {
+    use std::io;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from("Io {:?}: {}", 0.kind(), 0)]
+        Io(io::Error),
+        #[error_kind("Open {}: code {}", path.display(), self.code())]
+        Open { path: PathBuf },
+        #[error_kind("Len {}", 0.len())]
+        Len(Vec<u8>),
+    }
+
+    impl E {
+        fn code(&self) -> u32 { 10 }
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.to_string().as_str(), "Io NotFound: entity not found");
+
+    let e = E::Open { path: PathBuf::from("/tmp/x") };
+    assert_eq!(e.to_string().as_str(), "Open /tmp/x: code 10");
+
+    assert_eq!(E::Len(vec![1, 2]).to_string().as_str(), "Len 2");
+}
+
+
+#[test]
+fn test_error_format_spec() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("code {:#06x} name {:>6} data {:?}", 0, 1, 2)]
+        Code(u16, String, Vec<u8>),
+        #[error_kind("[{0:>1$}]")]
+        Width(String, usize),
+        #[error_kind("[{name:<width$}]")]
+        Named { name: String, width: usize },
+    }
+
+    let e = E::Code(0x1f, "test".to_owned(), vec![1, 2]);
+    assert_eq!(e.to_string().as_str(), "code 0x001f name   test data [1, 2]");
+
+    let e = E::Width("a".to_owned(), 3);
+    assert_eq!(e.to_string().as_str(), "[  a]");
+
+    let e = E::Named { name: "a".to_owned(), width: 3 };
+    assert_eq!(e.to_string().as_str(), "[a  ]");
+}
+
+
+#[test]
+fn test_error_format_precision() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("value {:.*} of {}", 0, 1, 2)]
+        Value(usize, f64, String),
+        #[error_kind("value {1:.*}", 0, 1)]
+        Position(usize, f64),
+    }
+
+    let e = E::Value(2, 1.2345, "sensor".to_owned());
+    assert_eq!(e.to_string().as_str(), "value 1.23 of sensor");
+
+    let e = E::Position(1, 2.25);
+    assert_eq!(e.to_string().as_str(), "value 2.2");
+}
+
+
+#[test]
+fn test_error_doc() {
+    #[derive(Debug, Error)]
+    #[error_prefix = "Config"]
+    enum E {
+        /// missing key {key}
+        /// in section {section}
+        #[error_doc]
+        MissingKey { key: String, section: String },
+        /// connection closed
+        #[error_doc]
+        Closed,
+    }
+
+    let e = E::MissingKey { key: "port".to_owned(), section: "server".to_owned() };
+    assert_eq!(e.to_string().as_str(), "Config: missing key port in section server");
+    assert_eq!(E::Closed.to_string().as_str(), "Config: connection closed");
+}
+
+
+#[test]
+fn test_error_alternate_chain() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "Config"]
+    enum ConfigError {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    enum AppError {
+        #[error_from]
+        Config(ConfigError),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: AppError = ConfigError::from(io::Error::from(io::ErrorKind::NotFound)).into();
+    assert_eq!(format!("{:#}", e).as_str(),
+        "App: Config: entity not found\n    Caused by: Config: entity not found\n    Caused by: entity not found");
+    assert_eq!(format!("{:#}", AppError::Closed).as_str(), "App: closed");
+}
+
+
+#[test]
+fn test_error_prefix_auto() {
+    mod parser {
+        use error_rules::*;
+
+        #[derive(Debug, Error)]
+        #[error_prefix(auto)]
+        pub enum Error {
+            #[error_kind("bad token")]
+            BadToken,
+        }
+
+        #[derive(Debug, Error)]
+        #[error_prefix(auto)]
+        pub enum LexerError {
+            #[error_kind("bad char")]
+            BadChar,
+        }
+    }
+
+    assert_eq!(parser::Error::BadToken.to_string().as_str(), "parser: bad token");
+    assert_eq!(parser::LexerError::BadChar.to_string().as_str(), "Lexer: bad char");
+}
+
+
+#[test]
+fn test_error_path_display() {
+    use std::path::PathBuf;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("cannot open {}", 0)]
+        Open(PathBuf),
+        #[error_kind("cannot read {0}")]
+        Read(PathBuf),
+        #[error_kind("cannot write {path}: {}", code)]
+        Write { path: PathBuf, code: i32 },
+    }
+
+    assert_eq!(E::Open(PathBuf::from("/tmp/a")).to_string().as_str(), "cannot open /tmp/a");
+    assert_eq!(E::Read(PathBuf::from("/tmp/b")).to_string().as_str(), "cannot read /tmp/b");
+
+    let e = E::Write { path: PathBuf::from("/tmp/c"), code: 5 };
+    assert_eq!(e.to_string().as_str(), "cannot write /tmp/c: 5");
+}
+
+
+#[test]
+fn test_error_path_display_custom() {
+    use std::fmt;
+
+    #[derive(Debug)]
+    struct Path(&'static str);
+
+    impl fmt::Display for Path {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "path:{}", self.0)
+        }
+    }
+
+    #[derive(Debug, Error)]
+    enum E<'a> {
+        #[error_kind("cannot open {}", 0)]
+        Open(Path),
+        #[error_kind("cannot read {0}")]
+        Read(&'a std::path::Path),
+    }
+
+    assert_eq!(E::Open(Path("a")).to_string().as_str(), "cannot open path:a");
+    let path = std::path::Path::new("/tmp/b");
+    assert_eq!(E::Read(path).to_string().as_str(), "cannot read /tmp/b");
+}
+
+
+#[test]
+fn test_error_constructors() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_constructors]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("http {}: {}", 0, 1)]
+        HttpStatus(u16, String),
+        #[error_kind("type {}", name)]
+        Type { name: String },
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e = E::io(io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(e.to_string().as_str(), "entity not found");
+    assert_eq!(E::http_status(404, "not found".to_owned()).to_string().as_str(), "http 404: not found");
+    assert_eq!(E::r#type("u8".to_owned()).to_string().as_str(), "type u8");
+    assert_eq!(E::closed().to_string().as_str(), "closed");
+}
+
+
+#[test]
+fn test_error_accessors() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_accessors]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("http {}: {}", 0, 1)]
+        HttpStatus(u16, String),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let mut e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert!(e.is_io());
+    assert!(! e.is_closed());
+    assert_eq!(e.as_io().map(io::Error::kind), Some(io::ErrorKind::NotFound));
+    assert!(e.as_io_mut().is_some());
+    assert!(e.as_http_status().is_none());
+
+    let mut e = E::HttpStatus(404, "not found".to_owned());
+    if let Some((code, _)) = e.as_http_status_mut() {
+        *code = 500;
+    }
+    assert_eq!(e.as_http_status(), Some((&500, &"not found".to_owned())));
+    assert!(E::Closed.is_closed());
+}
+
+
+#[test]
+fn test_error_snake_case() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_constructors]
+    #[error_accessors]
+    enum E {
+        #[error_from]
+        IO(io::Error),
+        #[error_kind("http {}", 0)]
+        HTTPError(u16),
+        #[error_builder]
+        #[error_kind("type {}", name)]
+        Type { name: String },
+    }
+
+    let e = E::io(io::Error::from(io::ErrorKind::NotFound));
+    assert!(e.is_io());
+    assert!(e.as_io().is_some());
+
+    let e = E::http_error(404);
+    assert!(e.is_http_error());
+    assert_eq!(e.as_http_error(), Some(&404));
+
+    let e = E::r#type().name("u8".to_owned()).build().unwrap();
+    assert!(e.is_type());
+    assert_eq!(e.to_string().as_str(), "type u8");
+}
+
+
+#[test]
+fn test_error_into_source() {
+    use std::io;
+
+    type Result<T> = std::result::Result<T, E>;
+
+    #[derive(Debug, Error)]
+    #[error_accessors]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    #[derive(Debug, Error)]
+    #[error_accessors]
+    enum Single {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.into_io().unwrap().kind(), io::ErrorKind::NotFound);
+    assert!(E::Closed.into_io().unwrap_err().is_closed());
+
+    fn close() -> Result<()> {
+        Err(E::Closed)
+    }
+    assert!(close().unwrap_err().into_io().is_err());
+
+    let e: Single = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.into_inner().kind(), io::ErrorKind::NotFound);
+}
+
+
+#[test]
+fn test_error_kind_enum() {
+    use std::io;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Error)]
+    #[error_kind_enum]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let list: Vec<E> = vec![
+        io::Error::from(io::ErrorKind::NotFound).into(),
+        io::Error::from(io::ErrorKind::TimedOut).into(),
+        E::Closed,
+    ];
+
+    let mut count = HashMap::new();
+    for e in &list {
+        *count.entry(e.kind()).or_insert(0) += 1;
+    }
+    assert_eq!(count.get(&EKind::Io), Some(&2));
+    assert_eq!(count.get(&EKind::Closed), Some(&1));
+
+    assert!(list[0] == EKind::Io);
+    assert!(list[2] != EKind::Io);
+    assert!(matches!(&list[2], e if *e == EKind::Closed));
+}
+
+
+#[test]
+fn test_error_chain() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_prefix = "Config"]
+    enum ConfigError {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error_chain]
+    #[error_prefix = "App"]
+    enum AppError {
+        #[error_from]
+        Config(ConfigError),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: AppError = ConfigError::from(io::Error::from(io::ErrorKind::NotFound)).into();
+    let chain: Vec<String> = e.iter_chain().map(|v| v.to_string()).collect();
+    assert_eq!(chain, vec![
+        "App: Config: entity not found",
+        "Config: entity not found",
+        "entity not found",
+    ]);
+    assert!(e.root_cause().downcast_ref::<io::Error>().is_some());
+
+    assert_eq!(AppError::Closed.iter_chain().count(), 1);
+    assert_eq!(AppError::Closed.root_cause().to_string().as_str(), "App: closed");
+}
+
+
+#[test]
+fn test_error_display_chain() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_chain]
+    #[error_prefix = "App"]
+    enum E {
+        #[error_from("read failed")]
+        Io(io::Error),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.display_chain().to_string().as_str(), "App: read failed: entity not found");
+    assert_eq!(E::Closed.display_chain().to_string().as_str(), "App: closed");
+}
+
+
+#[test]
+fn test_error_result() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_result = "AppResult"]
+    enum AppError {
+        #[error_from]
+        Io(io::Error),
+    }
+
+    #[derive(Debug, Error)]
+    #[error_result = "StoreResult"]
+    enum StoreError<B: std::error::Error + 'static> {
+        #[error_from]
+        Backend(B),
+    }
+
+    fn open() -> AppResult<()> {
+        Err(io::Error::from(io::ErrorKind::NotFound))?
+    }
+
+    #[derive(Debug, Error)]
+    #[error_result = "ParseResult"]
+    enum ParseError<'a, T: std::fmt::Debug + std::fmt::Display> {
+        #[error_kind("invalid {} at {}", 0, 1)]
+        Invalid(T, &'a str),
+    }
+
+    fn get() -> StoreResult<u8, io::Error> {
+        Err(io::Error::from(io::ErrorKind::NotFound))?
+    }
+
+    fn parse(text: &str) -> ParseResult<'_, u8, char> {
+        Err(ParseError::Invalid('x', text))
+    }
+
+    assert_eq!(open().unwrap_err().to_string().as_str(), "entity not found");
+    assert_eq!(get().unwrap_err().to_string().as_str(), "entity not found");
+    assert_eq!(parse("line 1").unwrap_err().to_string().as_str(), "invalid x at line 1");
+}
+
+
+#[test]
+fn test_error_variant_name() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_variant_name]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    #[derive(Debug, Error)]
+    enum Own {
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    impl Own {
+        fn variant_name(&self) -> String {
+            "own".to_owned()
+        }
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.variant_name(), "Io");
+    assert_eq!(E::Closed.variant_name(), "Closed");
+    assert_eq!(Own::Closed.variant_name().as_str(), "own");
+}
+
+
+#[test]
+fn test_error_code() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_code = 100]
+        #[error_from]
+        Io(io::Error),
+        #[error_code = 200]
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.code(), 100);
+    assert_eq!(E::Closed.code(), 200);
+    assert_eq!(E::CODES, &[(100, "Io"), (200, "Closed")]);
+}
+
+
+#[test]
+fn test_error_help() {
+    #[derive(Debug, Error)]
+    #[error_prefix = "App"]
+    enum E {
+        #[error_kind("file exists")]
+        #[error_help("try running with --force")]
+        Exists,
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    assert_eq!(E::Exists.help(), Some("try running with --force"));
+    assert_eq!(E::Closed.help(), None);
+    assert_eq!(E::Exists.to_string().as_str(), "App: file exists");
+    assert_eq!(format!("{:#}", E::Exists).as_str(), "App: file exists\n    Help: try running with --force");
+}
+
+
+#[test]
+fn test_error_backtrace() {
+    use std::io;
+    use std::backtrace::{Backtrace, BacktraceStatus};
+
+    #[derive(Debug, Error)]
+    #[error_constructors]
+    enum E {
+        #[error_from]
+        Io(io::Error, Backtrace),
+        #[error_kind("closed {}", code)]
+        Closed {
+            code: u8,
+            #[error_backtrace]
+            trace: Backtrace,
+        },
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.to_string().as_str(), "entity not found");
+    assert!(e.backtrace().is_some());
+
+    let e = E::closed(1);
+    assert_eq!(e.to_string().as_str(), "closed 1");
+    let status = e.backtrace().unwrap().status();
+    assert!(matches!(status, BacktraceStatus::Captured | BacktraceStatus::Disabled));
+}
+
+
+#[test]
+fn test_error_location() {
+    use std::io;
+    use std::panic::Location;
+
+    #[derive(Debug, Error)]
+    #[error_constructors]
+    enum E {
+        #[error_from]
+        Io(io::Error, &'static Location<'static>),
+        #[error_kind("closed {}", code)]
+        Closed {
+            code: u8,
+            #[error_location]
+            at: &'static Location<'static>,
+        },
+    }
+
+    fn open() -> Result<(), E> {
+        Err(io::Error::from(io::ErrorKind::NotFound))?;
+        Ok(())
+    }
+
+    let line = line!() - 4;
+    let e = open().unwrap_err();
+    let location = e.location().unwrap();
+    assert_eq!(location.file(), file!());
+    assert_eq!(location.line(), line);
+
+    let line = line!() + 1;
+    let e = E::closed(1);
+    assert_eq!(e.location().unwrap().line(), line);
+    assert_eq!(e.to_string().as_str(), "closed 1");
+    assert_eq!(format!("{:#}", e), format!("closed 1\n    at {}:{}:13", file!(), line));
+}
+
+
+#[test]
+fn test_error_severity() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("retry")]
+        #[error_severity(warn)]
+        Retry,
+        #[error_kind("crash")]
+        #[error_severity(fatal)]
+        Crash,
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    assert_eq!(E::Retry.severity(), ESeverity::Warn);
+    assert_eq!(E::Crash.severity(), ESeverity::Fatal);
+    assert_eq!(E::Failed.severity(), ESeverity::Error);
+    assert!(E::Retry.severity() < E::Failed.severity());
+    assert_eq!(E::Crash.severity().to_string().as_str(), "fatal");
+}
+
+
+#[test]
+fn test_error_exit_code() {
+    use std::process::{ExitCode, Termination};
+
+    #[derive(Debug, Error)]
+    #[error_termination]
+    enum E {
+        #[error_kind("usage")]
+        #[error_exit_code(2)]
+        Usage,
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    assert_eq!(E::Usage.exit_code(), ExitCode::from(2));
+    assert_eq!(E::Failed.exit_code(), ExitCode::FAILURE);
+
+    let exit: EExit = Err(E::Usage).into();
+    assert_eq!(exit.report(), ExitCode::from(2));
+    assert_eq!(EExit(Ok(())).report(), ExitCode::SUCCESS);
+}
+
+
+#[test]
+fn test_error_transient() {
+    #[derive(Debug, Error)]
+    enum DbError {
+        #[error_kind("connection lost")]
+        #[error_transient]
+        Connection,
+        #[error_kind("syntax error")]
+        Syntax,
+    }
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from]
+        #[error_transient(source)]
+        Db(DbError),
+        #[error_kind("busy")]
+        #[error_transient]
+        Busy,
+        #[error_kind("invalid")]
+        Invalid,
+    }
+
+    assert!(E::from(DbError::Connection).is_transient());
+    assert!(! E::from(DbError::Syntax).is_transient());
+    assert!(E::Busy.is_transient());
+    assert!(! E::Invalid.is_transient());
+}
+
+
+#[test]
+fn test_error_category() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("connection refused")]
+        #[error_category(Network)]
+        Refused,
+        #[error_kind("dns failed")]
+        #[error_category(Network)]
+        Dns,
+        #[error_kind("disk full")]
+        #[error_category(Storage)]
+        Disk,
+        #[error_kind("other")]
+        Other,
+    }
+
+    assert_eq!(E::Refused.category(), Some(ECategory::Network));
+    assert_eq!(E::Dns.category(), Some(ECategory::Network));
+    assert_eq!(E::Disk.category(), Some(ECategory::Storage));
+    assert_eq!(E::Other.category(), None);
+}
+
+
+#[test]
+fn test_error_url() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("license expired")]
+        #[error_help("renew license")]
+        #[error_url("https://example.com/kb/license")]
+        License,
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    assert_eq!(E::License.url(), Some("https://example.com/kb/license"));
+    assert_eq!(E::Failed.url(), None);
+    assert_eq!(format!("{:#}", E::License).as_str(),
+        "license expired\n    Help: renew license\n    See: https://example.com/kb/license");
+}
+
+
+#[test]
+fn test_error_optional_source() {
+    use std::io;
+    use std::error::Error as StdError;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("read failed")]
+        Read(#[error_source(optional)] Option<io::Error>),
+        #[error_kind("task {} failed", id)]
+        Task {
+            id: u32,
+            #[error_source(optional)]
+            cause: Option<Box<dyn StdError + Send + Sync>>,
+        },
+    }
+
+    let e = E::Read(Some(io::Error::from(io::ErrorKind::NotFound)));
+    assert_eq!(e.source().unwrap().to_string().as_str(), "entity not found");
+    assert!(E::Read(None).source().is_none());
+
+    let e = E::Task { id: 1, cause: Some("timeout".into()) };
+    assert_eq!(e.to_string().as_str(), "task 1 failed");
+    assert_eq!(e.source().unwrap().to_string().as_str(), "timeout");
+    assert!(E::Task { id: 2, cause: None }.source().is_none());
+}
+
+
+#[test]
+fn test_error_cfg() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_constructors]
+    #[error_accessors]
+    #[error_kind_enum]
+    #[error_eq_kind]
+    enum E {
+        #[error_from]
+        #[error_code = 1]
+        Io(io::Error),
+        #[cfg(not(test))]
+        #[error_from]
+        #[error_code = 2]
+        Missing(MissingError),
+        #[cfg(test)]
+        #[error_kind("closed {}", code)]
+        #[error_code = 3]
+        #[error_severity(warn)]
+        Closed { code: u8 },
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(e.code(), 1);
+    assert_eq!(E::CODES, &[(1, "Io"), (3, "Closed")]);
+
+    let e = E::closed(1);
+    assert_eq!(e.kind(), EKind::Closed);
+    assert_eq!(e.severity(), ESeverity::Warn);
+    assert_eq!(e.to_string().as_str(), "closed 1");
+}
+
+
+#[test]
+fn test_error_eq() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_eq]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("code {}", 0)]
+        Code(u16),
+    }
+
+    let e1: E = io::Error::from(io::ErrorKind::NotFound).into();
+    let e2: E = io::Error::from(io::ErrorKind::Other).into();
+    assert_eq!(e1, e2);
+    assert_eq!(E::Code(404), E::Code(404));
+    assert_ne!(E::Code(404), E::Code(500));
+    assert_ne!(E::Code(404), e1);
+}
+
+
+#[test]
+fn test_error_eq_total() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_eq(Eq)]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("code {}", 0)]
+        Code(u16),
+    }
+
+    fn assert_eq_total<T: Eq>(_: &T) {}
+    assert_eq_total(&E::Code(404));
+    assert_eq!(E::Code(404), E::Code(404));
+}
+
+
+#[test]
+fn test_error_clone() {
+    use std::io;
+    use std::sync::Arc;
+    use std::error::Error as StdError;
+
+    #[error_clone]
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_from("parse: {}", 0)]
+        Parse(Arc<std::num::ParseIntError>),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::NotFound).into();
+    let e2 = e.clone();
+    assert_eq!(e2.to_string().as_str(), "entity not found");
+    assert!(e2.source().unwrap().downcast_ref::<io::Error>().is_some());
+    if let E::Io(inner) = &e2 {
+        let _: &Arc<io::Error> = inner;
+    }
+
+    let e: E = "x".parse::<u8>().unwrap_err().into();
+    assert_eq!(e.clone().to_string().as_str(), "parse: invalid digit found in string");
+    assert_eq!(E::Closed.clone().to_string().as_str(), "closed");
+}
+
+
+#[test]
+fn test_error_clone_transparent() {
+    use std::io;
+    use std::error::Error as StdError;
+
+    #[derive(Debug, Error)]
+    #[error_kind("inner")]
+    struct Inner {
+        #[error_source(optional)]
+        source: Option<io::Error>,
+    }
+
+    #[error_clone]
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_transparent]
+        Io(io::Error),
+        #[error_kind("failed")]
+        Failed {
+            #[error_source(optional)]
+            source: Option<io::Error>,
+        },
+    }
+
+    let e: E = io::Error::other(Inner { source: None }).into();
+    let e2 = e.clone();
+    assert_eq!(e2.to_string().as_str(), "inner");
+    assert!(e2.source().is_none());
+
+    let e = E::Failed { source: Some(std::sync::Arc::new(io::ErrorKind::NotFound.into())) };
+    assert!(e.clone().source().unwrap().downcast_ref::<io::Error>().is_some());
+}
+
+
+#[test]
+fn test_error_clone_struct() {
+    use std::io;
+    use std::error::Error as StdError;
+
+    #[error_clone]
+    #[derive(Debug, Error)]
+    #[error_from("Config {}: {source}", path)]
+    struct ConfigError {
+        path: String,
+        #[error_source]
+        error: io::Error,
+    }
+
+    let e = ConfigError {
+        path: "/etc/app.conf".to_owned(),
+        error: std::sync::Arc::new(io::ErrorKind::NotFound.into()),
+    };
+    let e2 = e.clone();
+    assert_eq!(e2.to_string().as_str(), "Config /etc/app.conf: entity not found");
+    assert!(e2.source().unwrap().downcast_ref::<io::Error>().is_some());
+}
+
+
+#[test]
+fn test_error_no_std() {
+    use core::fmt;
+    use core::error::Error as _;
+
+    #[derive(Debug)]
+    struct BusError;
+
+    impl fmt::Display for BusError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("bus error")
+        }
+    }
+
+    impl core::error::Error for BusError {}
+
+    #[derive(Debug, Error)]
+    #[error_no_std]
+    #[error_prefix = "Sensor"]
+    #[error_constructors]
+    #[error_variant_name]
+    enum E {
+        #[error_from]
+        #[error_code = 1]
+        Bus(BusError),
+        #[error_from]
+        #[error_code = 2]
+        Utf8(std::string::FromUtf8Error),
+        #[error_kind("timeout {}ms", 0)]
+        #[error_code = 3]
+        #[error_severity(warn)]
+        Timeout(u32),
+    }
+
+    let e: E = BusError.into();
+    assert_eq!(e.to_string().as_str(), "Sensor: bus error");
+    assert!(e.source().is_some());
+    assert_eq!(E::Timeout(10).to_string().as_str(), "Sensor: timeout 10ms");
+    assert_eq!(E::Timeout(10).variant_name(), "Timeout");
+
+    let e: E = String::from_utf8(vec![0xFF]).unwrap_err().into();
+    assert_eq!(e.code(), 2);
+    assert_eq!(E::timeout(10).code(), 3);
+    assert_eq!(E::timeout(10).severity(), ESeverity::Warn);
+}
+
+
+#[test]
+fn test_error_ffi() {
+    #[derive(Debug, Error)]
+    #[error_ffi]
+    enum E {
+        #[error_kind("not found: {}", 0)]
+        #[error_code = 404]
+        NotFound(String),
+        #[error_kind("busy")]
+        #[error_code = 503]
+        Busy,
+    }
+
+    assert_eq!(E::Busy.ffi_code(), ECode::Busy);
+    assert_eq!(ECode::NotFound as i32, 404);
+
+    let (code, message) = E::NotFound("a\0b".to_owned()).to_ffi();
+    assert_eq!(code, 404);
+    assert_eq!(message.to_str().unwrap(), "not found: ab");
+}
+
+
+#[test]
+fn test_error_io_kind() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("config not found")]
+        #[error_io_kind(NotFound)]
+        Config,
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    let e: E = io::Error::from(io::ErrorKind::PermissionDenied).into();
+    assert_eq!(io::Error::from(e).kind(), io::ErrorKind::PermissionDenied);
+    assert_eq!(io::Error::from(E::Config).kind(), io::ErrorKind::NotFound);
+    assert_eq!(io::Error::from(E::Failed).kind(), io::ErrorKind::Other);
+}
+
+
+#[test]
+fn test_error_io_kind_source() {
+    use std::{
+        io,
+        sync::Arc,
+    };
+
+    mod studio {
+        #[derive(Debug)]
+        pub struct Error;
+
+        impl std::fmt::Display for Error {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("studio failed")
+            }
+        }
+
+        impl std::error::Error for Error {}
+    }
+
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_from]
+        Studio(studio::Error),
+        #[error_from]
+        Boxed(Box<io::Error>),
+        #[error_transparent]
+        Shared(Arc<io::Error>),
+    }
+
+    assert_eq!(io::Error::from(E::Studio(studio::Error)).kind(), io::ErrorKind::Other);
+
+    let e = E::Boxed(Box::new(io::ErrorKind::NotFound.into()));
+    assert_eq!(io::Error::from(e).kind(), io::ErrorKind::NotFound);
+
+    let e = E::Shared(Arc::new(io::ErrorKind::TimedOut.into()));
+    assert_eq!(io::Error::from(e).kind(), io::ErrorKind::TimedOut);
+}
+
+
+#[test]
+fn test_error_status() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("not found: {}", 0)]
+        #[error_status(404)]
+        NotFound(String),
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    assert_eq!(E::NotFound("a".to_owned()).status(), 404);
+    assert_eq!(E::Failed.status(), 500);
+}
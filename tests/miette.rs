@@ -0,0 +1,44 @@
+#![cfg(feature = "miette")]
+
+use error_rules::*;
+
+
+/// `miette::Diagnostic` accessors implemented by `#[error_diagnostic]`
+mod miette {
+    use std::fmt;
+
+    pub trait Diagnostic: std::error::Error {
+        fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> { None }
+        fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> { None }
+        fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> { None }
+    }
+}
+
+
+#[test]
+fn test_diagnostic() {
+    use miette::Diagnostic;
+
+    #[derive(Debug, Error)]
+    #[error_diagnostic]
+    enum AppError {
+        #[error_kind("config not found")]
+        #[error_code = 100]
+        #[error_help("create config with --init")]
+        #[error_url("https://example.com/kb/config")]
+        Config,
+        #[error_kind("closed")]
+        #[error_code = 200]
+        Closed,
+    }
+
+    let report: &dyn Diagnostic = &AppError::Config;
+    assert_eq!(report.code().unwrap().to_string().as_str(), "100");
+    assert_eq!(report.help().unwrap().to_string().as_str(), "create config with --init");
+    assert_eq!(report.url().unwrap().to_string().as_str(), "https://example.com/kb/config");
+
+    let report: &dyn Diagnostic = &AppError::Closed;
+    assert_eq!(report.code().unwrap().to_string().as_str(), "200");
+    assert!(report.help().is_none());
+    assert!(report.url().is_none());
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "problem-json")]
+
+use error_rules::*;
+
+
+#[test]
+fn test_problem_json() {
+    #[derive(Debug, Error)]
+    #[error_problem_json]
+    enum E {
+        #[error_kind("not found: {}", 0)]
+        #[error_status(404)]
+        #[error_code = 10]
+        #[error_url("https://example.com/not-found")]
+        NotFound(String),
+        #[error_kind("failed")]
+        #[error_code = 20]
+        Failed,
+    }
+
+    assert_eq!(E::NotFound("a \"b\"".to_owned()).problem_json(Some("/users/a")),
+        "{\"type\":\"https://example.com/not-found\",\"title\":\"NotFound\",\"status\":404,\
+        \"detail\":\"not found: a \\\"b\\\"\",\"instance\":\"/users/a\",\"code\":10}");
+    assert_eq!(E::Failed.problem_json(None),
+        "{\"type\":\"about:blank\",\"title\":\"Failed\",\"status\":500,\"detail\":\"failed\",\"code\":20}");
+}
+
+
+#[test]
+fn test_problem_json_opt_in() {
+    #[derive(Debug, Error)]
+    enum E {
+        #[error_kind("failed")]
+        Failed,
+    }
+
+    impl E {
+        fn status(&self) -> u16 { 503 }
+
+        fn problem_json(&self) -> &'static str { "custom" }
+    }
+
+    assert_eq!(E::Failed.status(), 503);
+    assert_eq!(E::Failed.problem_json(), "custom");
+}
@@ -0,0 +1,110 @@
+#![cfg(feature = "pyo3")]
+
+use error_rules::*;
+
+
+/// `pyo3` exceptions without Python interpreter: `PyErr` keeps
+/// the exception name and the message instead of the Python objects
+mod pyo3 {
+    #[derive(Debug)]
+    pub struct PyErr {
+        pub exception: &'static str,
+        pub message: String,
+    }
+
+    pub trait PyErrArguments {
+        fn arguments(self) -> String;
+    }
+
+    impl PyErrArguments for String {
+        fn arguments(self) -> String {
+            self
+        }
+    }
+
+    macro_rules! exception {
+        ( $( $name:ident ),* ) => {
+            $(
+                pub struct $name;
+
+                impl $name {
+                    pub fn new_err<A>(args: A) -> super::PyErr
+                    where
+                        A: super::PyErrArguments + Send + Sync + 'static,
+                    {
+                        super::PyErr { exception: stringify!($name), message: args.arguments() }
+                    }
+                }
+            )*
+        };
+    }
+
+    pub mod exceptions {
+        exception!(PyRuntimeError, PyValueError, PyConnectionError, PyOSError);
+    }
+}
+
+
+/// Custom Python exception like one from the `pyo3::create_exception!`
+struct ConfigError;
+
+impl ConfigError {
+    fn new_err<A>(args: A) -> pyo3::PyErr
+    where
+        A: pyo3::PyErrArguments + Send + Sync + 'static,
+    {
+        pyo3::PyErr { exception: "ConfigError", message: args.arguments() }
+    }
+}
+
+
+#[test]
+fn test_py_err() {
+    #[derive(Debug, Error)]
+    #[error_py(Network = "ConnectionError")]
+    enum AppError {
+        #[error_kind("invalid port: {}", 0)]
+        #[error_py(ValueError)]
+        Port(u32),
+        #[error_kind("bad config")]
+        #[error_py(crate::ConfigError)]
+        Config,
+        #[error_kind("connection refused")]
+        #[error_category(Network)]
+        Refused,
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: pyo3::PyErr = AppError::Port(0).into();
+    assert_eq!((e.exception, e.message.as_str()), ("PyValueError", "invalid port: 0"));
+
+    let e: pyo3::PyErr = AppError::Config.into();
+    assert_eq!((e.exception, e.message.as_str()), ("ConfigError", "bad config"));
+
+    let e: pyo3::PyErr = AppError::Refused.into();
+    assert_eq!((e.exception, e.message.as_str()), ("PyConnectionError", "connection refused"));
+
+    let e: pyo3::PyErr = AppError::Closed.into();
+    assert_eq!((e.exception, e.message.as_str()), ("PyRuntimeError", "closed"));
+}
+
+
+#[test]
+fn test_py_err_default() {
+    #[derive(Debug, Error)]
+    #[error_py(OSError)]
+    enum AppError {
+        #[error_kind("invalid port")]
+        #[error_py(ValueError)]
+        Port,
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let e: pyo3::PyErr = AppError::Port.into();
+    assert_eq!(e.exception, "PyValueError");
+
+    let e: pyo3::PyErr = AppError::Closed.into();
+    assert_eq!(e.exception, "PyOSError");
+}
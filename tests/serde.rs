@@ -0,0 +1,191 @@
+#![cfg(feature = "serde")]
+
+use error_rules::*;
+
+
+/// Subset of the `serde` traits with signatures of the `serde` 1.0
+/// used by the generated `Serialize` implementation, and minimal JSON writer
+mod serde {
+    pub trait Serialize {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer;
+    }
+
+    pub trait Serializer: Sized {
+        type Ok;
+        type Error;
+        type SerializeStruct: ser::SerializeStruct<Ok = Self::Ok, Error = Self::Error>;
+
+        fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error>;
+        fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error>;
+        fn collect_seq<I>(self, iter: I) -> Result<Self::Ok, Self::Error>
+        where
+            I: IntoIterator,
+            I::Item: Serialize;
+        fn serialize_struct(self, name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error>;
+    }
+
+    pub mod ser {
+        pub trait SerializeStruct {
+            type Ok;
+            type Error;
+
+            fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+            where
+                T: ?Sized + super::Serialize;
+
+            fn end(self) -> Result<Self::Ok, Self::Error>;
+        }
+    }
+
+    impl Serialize for str {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self)
+        }
+    }
+
+    impl Serialize for String {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self)
+        }
+    }
+
+    impl Serialize for u32 {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u32(*self)
+        }
+    }
+
+    impl<T: ?Sized + Serialize> Serialize for &T {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (**self).serialize(serializer)
+        }
+    }
+
+    impl<T: Serialize> Serialize for Vec<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self)
+        }
+    }
+
+    pub struct Json<'a>(pub &'a mut String);
+
+    impl<'a> Serializer for Json<'a> {
+        type Ok = ();
+        type Error = ();
+        type SerializeStruct = Json<'a>;
+
+        fn serialize_str(self, v: &str) -> Result<(), ()> {
+            self.0.push_str(&format!("{:?}", v));
+            Ok(())
+        }
+
+        fn serialize_u32(self, v: u32) -> Result<(), ()> {
+            self.0.push_str(&v.to_string());
+            Ok(())
+        }
+
+        fn collect_seq<I>(self, iter: I) -> Result<(), ()>
+        where
+            I: IntoIterator,
+            I::Item: Serialize,
+        {
+            self.0.push('[');
+            for (i, item) in iter.into_iter().enumerate() {
+                if i > 0 {
+                    self.0.push(',');
+                }
+                item.serialize(Json(&mut *self.0))?;
+            }
+            self.0.push(']');
+            Ok(())
+        }
+
+        fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Json<'a>, ()> {
+            self.0.push('{');
+            Ok(self)
+        }
+    }
+
+    impl ser::SerializeStruct for Json<'_> {
+        type Ok = ();
+        type Error = ();
+
+        fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), ()>
+        where
+            T: ?Sized + Serialize,
+        {
+            if ! self.0.ends_with('{') {
+                self.0.push(',');
+            }
+            self.0.push_str(&format!("{:?}:", key));
+            value.serialize(Json(self.0))
+        }
+
+        fn end(self) -> Result<(), ()> {
+            self.0.push('}');
+            Ok(())
+        }
+    }
+
+    pub fn to_string<T: Serialize>(value: &T) -> String {
+        let mut text = String::new();
+        value.serialize(Json(&mut text)).unwrap();
+        text
+    }
+}
+
+
+#[test]
+fn test_serialize() {
+    use std::io;
+
+    type Result<T> = std::result::Result<T, AppError>;
+
+    #[derive(Debug, Error)]
+    #[error_serialize]
+    enum AppError {
+        #[error_from]
+        #[error_code = 100]
+        Io(io::Error),
+        #[error_kind("closed")]
+        #[error_code = 200]
+        Closed,
+    }
+
+    fn open() -> Result<()> {
+        Err(io::Error::from(io::ErrorKind::NotFound).into())
+    }
+
+    let error = open().unwrap_err();
+    assert_eq!(serde::to_string(&error).as_str(),
+        r#"{"variant":"Io","message":"entity not found","code":100,"chain":["entity not found"]}"#);
+    assert_eq!(serde::to_string(&AppError::Closed).as_str(),
+        r#"{"variant":"Closed","message":"closed","code":200,"chain":[]}"#);
+}
+
+
+#[test]
+fn test_serialize_wire() {
+    #[derive(Debug, Error)]
+    #[error_serialize]
+    enum AppError {
+        #[error_kind("not found")]
+        #[error_wire(rename = "NOT_FOUND", since = "2")]
+        Missing,
+        #[error_kind("busy")]
+        #[error_wire(since = "3")]
+        Busy,
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    assert_eq!(serde::to_string(&AppError::Missing).as_str(),
+        r#"{"variant":"NOT_FOUND","message":"not found","chain":[]}"#);
+    assert_eq!(serde::to_string(&AppError::Busy).as_str(),
+        r#"{"variant":"Busy","message":"busy","chain":[]}"#);
+    assert_eq!(AppError::Missing.wire_since(), Some("2"));
+    assert_eq!(AppError::Busy.wire_since(), Some("3"));
+    assert_eq!(AppError::Closed.wire_since(), None);
+}
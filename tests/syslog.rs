@@ -0,0 +1,50 @@
+#![cfg(feature = "syslog")]
+
+use error_rules::*;
+
+
+#[test]
+fn test_syslog() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_syslog]
+    #[error_prefix = "App"]
+    enum AppError {
+        #[error_from]
+        #[error_severity(fatal)]
+        Io(io::Error),
+        #[error_kind("reconnect")]
+        #[error_severity(warn)]
+        Reconnect,
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let error: AppError = io::Error::from(io::ErrorKind::NotFound).into();
+    assert_eq!(error.severity().syslog_priority(), 2);
+    assert_eq!(error.syslog_message(3).as_str(), "<26>App: entity not found: entity not found");
+    assert_eq!(error.journal_fields(), vec![
+        ("PRIORITY", "2".to_owned()),
+        ("MESSAGE", "App: entity not found: entity not found".to_owned()),
+        ("ERROR_VARIANT", "Io".to_owned()),
+        ("ERROR_SOURCE", "entity not found".to_owned()),
+    ]);
+
+    assert_eq!(AppError::Reconnect.syslog_message(16).as_str(), "<132>App: reconnect");
+    assert_eq!(AppError::Closed.syslog_message(1).as_str(), "<11>App: closed");
+}
+
+
+#[test]
+fn test_syslog_without_severity() {
+    #[derive(Debug, Error)]
+    #[error_syslog]
+    enum AppError {
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    assert_eq!(AppError::Closed.severity(), AppErrorSeverity::Error);
+    assert_eq!(AppError::Closed.syslog_message(1).as_str(), "<11>closed");
+}
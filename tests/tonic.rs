@@ -0,0 +1,81 @@
+#![cfg(feature = "tonic")]
+
+use error_rules::*;
+
+
+/// `tonic::Code` variants used in the tests and `tonic::Status`
+mod tonic {
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Code {
+        Unknown,
+        NotFound,
+        Internal,
+        Unavailable,
+    }
+
+    pub struct Status {
+        code: Code,
+        message: String,
+    }
+
+    impl Status {
+        pub fn new(code: Code, message: impl Into<String>) -> Self {
+            Status { code, message: message.into() }
+        }
+
+        pub fn code(&self) -> Code { self.code }
+
+        pub fn message(&self) -> &str { &self.message }
+    }
+}
+
+
+#[test]
+fn test_status() {
+    #[derive(Debug, Error)]
+    #[error_grpc]
+    enum AppError {
+        #[error_kind("user {} not found", 0)]
+        #[error_grpc(NotFound)]
+        NotFound(u32),
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    let status: tonic::Status = AppError::NotFound(1).into();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(status.message(), "user 1 not found");
+
+    let status: tonic::Status = AppError::Closed.into();
+    assert_eq!(status.code(), tonic::Code::Unknown);
+    assert_eq!(status.message(), "closed");
+}
+
+
+#[test]
+fn test_status_default() {
+    #[derive(Debug, Error)]
+    #[error_grpc(Internal)]
+    enum AppError {
+        #[error_kind("busy")]
+        #[error_grpc(Unavailable)]
+        Busy,
+        #[error_kind("closed")]
+        Closed,
+    }
+
+    #[derive(Debug, Error)]
+    #[error_kind("user not found")]
+    #[error_grpc(NotFound)]
+    struct NotFoundError;
+
+    let status: tonic::Status = AppError::Busy.into();
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+
+    let status: tonic::Status = AppError::Closed.into();
+    assert_eq!(status.code(), tonic::Code::Internal);
+
+    let status: tonic::Status = NotFoundError.into();
+    assert_eq!(status.code(), tonic::Code::NotFound);
+    assert_eq!(status.message(), "user not found");
+}
@@ -0,0 +1,92 @@
+use error_rules::*;
+
+
+/// `tracing::event!` macro recording events with fields into the thread local list,
+/// `take()` returns and clears the events
+mod tracing {
+    use std::{
+        cell::RefCell,
+        fmt,
+    };
+
+    pub struct Level(&'static str);
+
+    impl Level {
+        pub const WARN: Level = Level("WARN");
+        pub const ERROR: Level = Level("ERROR");
+    }
+
+    impl fmt::Display for Level {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    thread_local! {
+        pub static EVENTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    macro_rules! event {
+        ($level:expr, variant = $variant:expr, location = %$location:expr, $($arg:tt)+) => {{
+            let event = format!("{} variant={} location={} {}",
+                $level, $variant, $location, format!($($arg)+));
+            $crate::tracing::EVENTS.with(|v| v.borrow_mut().push(event))
+        }};
+    }
+
+    pub(crate) use event;
+
+    pub fn take() -> Vec<String> {
+        EVENTS.with(|v| v.borrow_mut().split_off(0))
+    }
+}
+
+
+#[test]
+fn test_trace() {
+    use std::io;
+
+    #[derive(Debug, Error)]
+    #[error_trace(level = "warn")]
+    #[error_constructors]
+    enum AppError {
+        #[error_from]
+        Io(io::Error),
+        #[error_kind("closed: {}", 0)]
+        Closed(u8),
+    }
+
+    fn open() -> Result<(), AppError> {
+        Err(io::Error::from(io::ErrorKind::NotFound))?
+    }
+
+    let line = line!() + 1;
+    let error = AppError::closed(1);
+    assert_eq!(error.to_string().as_str(), "closed: 1");
+    assert!(open().is_err());
+
+    let events = tracing::take();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0], format!("WARN variant=Closed location={}:{}:17 closed: 1", file!(), line));
+    assert!(events[1].starts_with("WARN variant=Io location="));
+    assert!(events[1].ends_with(" entity not found"));
+}
+
+
+#[test]
+fn test_trace_default_level() {
+    #[derive(Debug, Error)]
+    #[error_trace]
+    enum AppError {
+        #[error_from]
+        Utf8(std::str::Utf8Error),
+    }
+
+    let data = vec![0xFF];
+    let error: AppError = std::str::from_utf8(&data).unwrap_err().into();
+    assert!(error.to_string().starts_with("invalid utf-8"));
+
+    let events = tracing::take();
+    assert_eq!(events.len(), 1);
+    assert!(events[0].starts_with("ERROR variant=Utf8 "));
+}
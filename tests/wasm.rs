@@ -0,0 +1,173 @@
+#![cfg(feature = "wasm")]
+
+use error_rules::*;
+
+
+/// `wasm-bindgen` value model without JavaScript runtime.
+/// Objects and arrays shared between clones like JavaScript references
+mod wasm_bindgen {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+    };
+
+    #[derive(Debug, Clone)]
+    pub enum JsValue {
+        String(String),
+        Number(f64),
+        Object(Rc<RefCell<Vec<(String, JsValue)>>>),
+        Array(Rc<RefCell<Vec<JsValue>>>),
+    }
+
+    impl JsValue {
+        pub fn from_str(v: &str) -> Self {
+            JsValue::String(v.to_owned())
+        }
+
+        pub fn as_string(&self) -> Option<String> {
+            match self {
+                JsValue::String(v) => Some(v.clone()),
+                _ => None,
+            }
+        }
+    }
+
+    impl From<u32> for JsValue {
+        fn from(v: u32) -> Self {
+            JsValue::Number(f64::from(v))
+        }
+    }
+}
+
+
+/// `js-sys` objects and `JSON.stringify()` over the `wasm_bindgen::JsValue` model
+mod js_sys {
+    use std::{
+        cell::RefCell,
+        ops::Deref,
+        rc::Rc,
+    };
+    use crate::wasm_bindgen::JsValue;
+
+    pub struct Object(JsValue);
+
+    impl Object {
+        pub fn new() -> Self {
+            Object(JsValue::Object(Rc::new(RefCell::new(Vec::new()))))
+        }
+    }
+
+    impl Deref for Object {
+        type Target = JsValue;
+
+        fn deref(&self) -> &JsValue {
+            &self.0
+        }
+    }
+
+    impl From<Object> for JsValue {
+        fn from(object: Object) -> Self {
+            object.0
+        }
+    }
+
+    pub struct Array(JsValue);
+
+    impl Array {
+        pub fn new() -> Self {
+            Array(JsValue::Array(Rc::new(RefCell::new(Vec::new()))))
+        }
+
+        pub fn push(&self, value: &JsValue) -> u32 {
+            match &self.0 {
+                JsValue::Array(v) => {
+                    let mut list = v.borrow_mut();
+                    list.push(value.clone());
+                    list.len() as u32
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    impl From<Array> for JsValue {
+        fn from(array: Array) -> Self {
+            array.0
+        }
+    }
+
+    pub struct Reflect;
+
+    impl Reflect {
+        pub fn set(target: &JsValue, key: &JsValue, value: &JsValue) -> Result<bool, JsValue> {
+            match (target, key.as_string()) {
+                (JsValue::Object(v), Some(key)) => {
+                    v.borrow_mut().push((key, value.clone()));
+                    Ok(true)
+                }
+                _ => Err(JsValue::from_str("TypeError")),
+            }
+        }
+    }
+
+    pub struct JsString(String);
+
+    impl From<JsString> for String {
+        fn from(v: JsString) -> String {
+            v.0
+        }
+    }
+
+    #[allow(non_snake_case)]
+    pub mod JSON {
+        use crate::wasm_bindgen::JsValue;
+        use super::JsString;
+
+        pub fn stringify(value: &JsValue) -> Result<JsString, JsValue> {
+            Ok(JsString(super::stringify(value)))
+        }
+    }
+
+    fn stringify(value: &JsValue) -> String {
+        match value {
+            JsValue::String(v) => format!("{:?}", v),
+            JsValue::Number(v) => v.to_string(),
+            JsValue::Object(v) => {
+                let list: Vec<String> = v.borrow().iter()
+                    .map(|(k, v)| format!("{:?}:{}", k, stringify(v)))
+                    .collect();
+                format!("{{{}}}", list.join(","))
+            }
+            JsValue::Array(v) => {
+                let list: Vec<String> = v.borrow().iter().map(stringify).collect();
+                format!("[{}]", list.join(","))
+            }
+        }
+    }
+}
+
+
+#[test]
+fn test_wasm() {
+    use std::io;
+    use wasm_bindgen::JsValue;
+
+    #[derive(Debug, Error)]
+    #[error_wasm]
+    enum AppError {
+        #[error_from]
+        #[error_code = 500]
+        Io(io::Error),
+        #[error_kind("invalid input")]
+        #[error_code = 400]
+        Input,
+    }
+
+    let value: JsValue = AppError::Input.into();
+    assert_eq!(String::from(js_sys::JSON::stringify(&value).unwrap()).as_str(),
+        r#"{"variant":"Input","message":"invalid input","code":400,"chain":[]}"#);
+
+    let value: JsValue = AppError::from(io::Error::from(io::ErrorKind::NotFound)).into();
+    assert_eq!(String::from(js_sys::JSON::stringify(&value).unwrap()).as_str(),
+        r#"{"variant":"Io","message":"entity not found","code":500,"chain":["entity not found"]}"#);
+}